@@ -0,0 +1,151 @@
+//! Benchmarks for the scaling of [DisputeSolver::available_moves] with the size of a game.
+
+#[macro_use]
+extern crate criterion;
+extern crate alloy_primitives;
+extern crate durin_fault;
+extern crate durin_primitives;
+
+use alloy_primitives::Address;
+use criterion::{BatchSize, BenchmarkId, Criterion, Throughput};
+use durin_fault::{
+    AlphabetTraceProvider, ClaimData, FaultDisputeSolver, FaultDisputeState, Gindex,
+    MultiGameSolver, Position, ProposerSolver, TraceProvider,
+};
+use durin_primitives::{Claim, DisputeSolver, GameStatus};
+use std::{
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+/// The max depth of the position trees of the synthetic games.
+const MAX_DEPTH: u8 = 16;
+
+/// A [TraceProvider] that serves the alphabet trace, sleeping before each state hash fetch to
+/// model a slow backend.
+struct SlowAlphabetProvider {
+    inner: AlphabetTraceProvider,
+    delay: Duration,
+}
+
+impl TraceProvider<[u8; 1]> for SlowAlphabetProvider {
+    fn absolute_prestate(&self) -> Arc<[u8; 1]> {
+        self.inner.absolute_prestate()
+    }
+
+    fn absolute_prestate_hash(&self) -> Claim {
+        self.inner.absolute_prestate_hash()
+    }
+
+    fn state_at(&self, position: Position) -> anyhow::Result<Arc<[u8; 1]>> {
+        self.inner.state_at(position)
+    }
+
+    fn state_hash(&self, position: Position) -> anyhow::Result<Claim> {
+        thread::sleep(self.delay);
+        self.inner.state_hash(position)
+    }
+
+    fn proof_at(&self, position: Position) -> anyhow::Result<Arc<[u8]>> {
+        self.inner.proof_at(position)
+    }
+
+    fn max_depth(&self) -> Option<u8> {
+        self.inner.max_depth()
+    }
+}
+
+/// Builds a synthetic game with the given number of claims, arranged as a balanced tree of
+/// alternating attacks and defenses beneath an honest root claim.
+fn synthetic_state(provider: &AlphabetTraceProvider, claims: usize) -> FaultDisputeState {
+    let root_claim = provider.state_hash(Position::ROOT).unwrap();
+    let mut state = Vec::<ClaimData>::with_capacity(claims);
+    for i in 0..claims {
+        let (parent_index, position) = match i {
+            0 => (u32::MAX, Position::ROOT),
+            _ => {
+                let parent_index = (i - 1) / 2;
+                let position = state[parent_index].position.make_move(i % 2 == 1);
+                (parent_index as u32, position)
+            }
+        };
+        state.push(ClaimData {
+            parent_index,
+            visited: false,
+            value: Claim::repeat_byte(i as u8),
+            position,
+            clock: 0,
+            claimant: Address::ZERO,
+            countered_by: Address::ZERO,
+        });
+    }
+    FaultDisputeState::new(state, root_claim, GameStatus::InProgress, MAX_DEPTH)
+}
+
+fn available_moves(c: &mut Criterion) {
+    let provider = AlphabetTraceProvider::new(b'a', MAX_DEPTH);
+    let solver = FaultDisputeSolver::new(ProposerSolver::new(AlphabetTraceProvider::new(
+        b'a', MAX_DEPTH,
+    )));
+
+    let mut group = c.benchmark_group("available_moves");
+    for claims in [100, 1_000, 10_000] {
+        let state = synthetic_state(&provider, claims);
+        group.throughput(Throughput::Elements(claims as u64));
+        group.bench_with_input(BenchmarkId::from_parameter(claims), &state, |b, state| {
+            b.iter_batched_ref(
+                || state.clone(),
+                |state| solver.available_moves(state).unwrap(),
+                BatchSize::LargeInput,
+            )
+        });
+    }
+    group.finish();
+}
+
+fn available_moves_latency(c: &mut Criterion) {
+    let provider = AlphabetTraceProvider::new(b'a', MAX_DEPTH);
+    let games = (0..8)
+        .map(|_| synthetic_state(&provider, 8))
+        .collect::<Vec<_>>();
+
+    let mut group = c.benchmark_group("available_moves_latency");
+    group.sample_size(10);
+    for concurrency in [1, 8] {
+        let solver = FaultDisputeSolver::new(ProposerSolver::new(SlowAlphabetProvider {
+            inner: AlphabetTraceProvider::new(b'a', MAX_DEPTH),
+            delay: Duration::from_millis(1),
+        }));
+        let multi = MultiGameSolver::new(
+            solver,
+            games
+                .iter()
+                .map(|g| Arc::new(Mutex::new(g.clone())))
+                .collect(),
+            concurrency,
+        );
+        group.bench_with_input(
+            BenchmarkId::from_parameter(concurrency),
+            &multi,
+            |b, multi| {
+                b.iter_batched(
+                    // Reset the games so that every iteration solves them from scratch.
+                    || {
+                        multi
+                            .games
+                            .iter()
+                            .zip(&games)
+                            .for_each(|(game, fresh)| *game.lock().unwrap() = fresh.clone())
+                    },
+                    |_| multi.available_moves_all(),
+                    BatchSize::SmallInput,
+                )
+            },
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, available_moves, available_moves_latency);
+criterion_main!(benches);
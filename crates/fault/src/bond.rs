@@ -0,0 +1,263 @@
+//! This module contains a port of the bond curve of the `FaultDisputeGame` contract, which
+//! scales the bond required to make a move exponentially with the depth of its position.
+
+use crate::{compute_gindex, Gindex, Position};
+use alloy_primitives::{I256, U128};
+
+/// The base fee assumed by the bond curve, in wei.
+const ASSUMED_BASE_FEE: u128 = 200_000_000_000;
+
+/// The gas charged for a move at the root of the position tree.
+const BASE_GAS_CHARGED: u128 = 400_000;
+
+/// The gas charged for a move at the max depth of the position tree.
+const HIGH_GAS_CHARGED: u128 = 300_000_000;
+
+/// The scale of the fixed point numbers used by the bond curve, i.e. `1.0`.
+const WAD: i128 = 1_000_000_000_000_000_000;
+
+/// Computes the bond required to make a move to the given [Position], as computed by
+/// `getRequiredBond` in the `FaultDisputeGame` contract. The gas charged for a move grows
+/// by a fixed multiplier at each level of the position tree, from [BASE_GAS_CHARGED] at the
+/// root to [HIGH_GAS_CHARGED] at the max depth, and is paid at [ASSUMED_BASE_FEE].
+///
+/// The curve is evaluated with the same fixed point approximations as the contract, so that
+/// the result matches the `msg.value` that the contract expects to the wei.
+///
+/// ### Takes
+/// - `position`: The position of the move.
+/// - `max_game_depth`: The max depth of the position tree.
+///
+/// ### Returns
+/// - `U128` or [Err]: The required bond, in wei, or an error if the position is invalid or
+///   deeper than the max depth of the position tree.
+pub fn required_bond(position: Position, max_game_depth: u8) -> anyhow::Result<U128> {
+    if max_game_depth == 0 {
+        anyhow::bail!("Max game depth must be non-zero");
+    }
+    if !position.is_valid() || position.depth() > max_game_depth {
+        anyhow::bail!("Game depth exceeded at position {}", position);
+    }
+    let depth = position.depth();
+    let wad = I256::unchecked_from(WAD);
+
+    // The multiplier is `(highGasCharged / baseGasCharged) ** (1 / MAX_GAME_DEPTH)`, computed
+    // as `e ** (ln(a) * (b / c))`.
+    let a = I256::unchecked_from(HIGH_GAS_CHARGED / BASE_GAS_CHARGED);
+    let c = I256::unchecked_from(max_game_depth) * wad;
+    let ln_a = ln_wad(a * wad);
+    let b_over_c = wad * wad / c;
+    let base = exp_wad(ln_a * b_over_c / wad);
+
+    // The gas charged at the depth is `baseGasCharged * base ** depth`.
+    let raw_gas = exp_wad(ln_wad(base) * (I256::unchecked_from(depth) * wad) / wad);
+    let required_gas = I256::unchecked_from(BASE_GAS_CHARGED) * raw_gas / wad;
+
+    Ok(U128::from(ASSUMED_BASE_FEE) * U128::from(required_gas.into_raw()))
+}
+
+/// Computes the deepest level of the position tree that can be reached with the given balance,
+/// posting one move at every depth from the root's children down, with the bond at each depth
+/// given by [required_bond].
+///
+/// ### Takes
+/// - `balance`: The balance available to pay bonds with, in wei.
+/// - `max_game_depth`: The max depth of the position tree.
+///
+/// ### Returns
+/// - `u8`: The deepest depth whose cumulative bonds fit within the balance, or `0` if not
+///   even a move at depth 1 can be afforded.
+pub fn affordable_depth(balance: U128, max_game_depth: u8) -> u8 {
+    let mut spent = U128::ZERO;
+    for depth in 1..=max_game_depth {
+        let bond = required_bond(compute_gindex(depth, 0), max_game_depth)
+            .expect("Depth is within the max game depth");
+        spent = spent.saturating_add(bond);
+        if spent > balance {
+            return depth - 1;
+        }
+    }
+    max_game_depth
+}
+
+/// Parses a decimal constant of the fixed point approximations.
+fn int(value: &str) -> I256 {
+    I256::from_dec_str(value).expect("Valid constant")
+}
+
+/// Multiplies two signed integers and arithmetically shifts the product right by 96 bits,
+/// wrapping on overflow as the EVM does.
+fn mul_shr_96(a: I256, b: I256) -> I256 {
+    a.wrapping_mul(b).asr(96)
+}
+
+/// Computes the natural logarithm of a positive 18 decimal fixed point number, as computed by
+/// `lnWad` in Solady's `FixedPointMathLib`, which the contract's bond curve is built on.
+fn ln_wad(x: I256) -> I256 {
+    // Reduce the range of x to (1, 2) * 2**96, with ln(2**k * x) = k * ln(2) + ln(x).
+    let t = x.leading_zeros();
+    let x = I256::from_raw((x.into_raw() << t) >> 159);
+
+    // Evaluate using an (8, 8)-term rational approximation, with p made monic.
+    let mut p = x.wrapping_add(int("3273285459638523848632254066296"));
+    p = mul_shr_96(p, x).wrapping_add(int("24828157081833163892658089445524"));
+    p = mul_shr_96(p, x).wrapping_add(int("43456485725739037958740375743393"));
+    p = mul_shr_96(p, x).wrapping_sub(int("11111509109440967052023855526967"));
+    p = mul_shr_96(p, x).wrapping_sub(int("45023709667254063763336534515857"));
+    p = mul_shr_96(p, x).wrapping_sub(int("14706773417378608786704636184526"));
+    p = p
+        .wrapping_mul(x)
+        .wrapping_sub(int("795164235651350426258249787498").wrapping_shl(96));
+
+    let mut q = x.wrapping_add(int("5573035233440673466300451813936"));
+    q = mul_shr_96(q, x).wrapping_add(int("71694874799317883764090561454958"));
+    q = mul_shr_96(q, x).wrapping_add(int("283447036172924575727196451306956"));
+    q = mul_shr_96(q, x).wrapping_add(int("401686690394027663651624208769553"));
+    q = mul_shr_96(q, x).wrapping_add(int("204048457590392012362485061816622"));
+    q = mul_shr_96(q, x).wrapping_add(int("31853899698501571402653359427138"));
+    q = mul_shr_96(q, x).wrapping_add(int("909429971244387300277376558375"));
+
+    // Scale the result, add ln(2**96 / 10**18) and k * ln(2), and convert back to 10**18.
+    let r = p
+        .wrapping_div(q)
+        .wrapping_mul(int("1677202110996718588342820967067443963516166"))
+        .wrapping_add(
+            int("16597577552685614221487285958193947469193820559219878177908093499208371")
+                .wrapping_mul(I256::unchecked_from(159 - t as i64)),
+        )
+        .wrapping_add(int(
+            "600920179829731861736702779321621459595472258049074101567377883020018308",
+        ));
+    r.asr(174)
+}
+
+/// Computes `e ** x` for an 18 decimal fixed point number, as computed by `expWad` in Solady's
+/// `FixedPointMathLib`. Results below `0.5e-18` round down to zero.
+fn exp_wad(x: I256) -> I256 {
+    if x <= int("-42139678854452767551") {
+        return I256::ZERO;
+    }
+
+    // Convert x to a 2**96 basis, and reduce its range to (-ln(2) / 2, ln(2) / 2) * 2**96 by
+    // factoring out powers of two, with exp(x) = exp(x') * 2**k.
+    let ln_2 = int("54916777467707473351141471128");
+    let x = x
+        .wrapping_shl(78)
+        .wrapping_div(I256::unchecked_from(3_814_697_265_625i64));
+    let k = x
+        .wrapping_shl(96)
+        .wrapping_div(ln_2)
+        .wrapping_add(I256::ONE.wrapping_shl(95))
+        .asr(96);
+    let x = x.wrapping_sub(k.wrapping_mul(ln_2));
+
+    // Evaluate using a (6, 7)-term rational approximation, with p made monic.
+    let mut y = x.wrapping_add(int("1346386616545796478920950773328"));
+    y = mul_shr_96(y, x).wrapping_add(int("57155421227552351082224309758442"));
+    let mut p = y
+        .wrapping_add(x)
+        .wrapping_sub(int("94201549194550492254356042504812"));
+    p = mul_shr_96(p, y).wrapping_add(int("28719021644029726153956944680412240"));
+    p = p
+        .wrapping_mul(x)
+        .wrapping_add(int("4385272521454847904659076985693276").wrapping_shl(96));
+
+    let mut q = x.wrapping_sub(int("2855989394907223263936484059900"));
+    q = mul_shr_96(q, x).wrapping_add(int("50020603652535783019961831881945"));
+    q = mul_shr_96(q, x).wrapping_sub(int("533845033583426703283633433725380"));
+    q = mul_shr_96(q, x).wrapping_add(int("3604857256930695427073651918091429"));
+    q = mul_shr_96(q, x).wrapping_sub(int("14423608567350463180887372962807573"));
+    q = mul_shr_96(q, x).wrapping_add(int("26449188498355588339934803723976023"));
+
+    // Scale the result, multiply by 2**k, and convert back to 10**18, all at once.
+    let r = p.wrapping_div(q).into_raw();
+    let scale = int("3822833074963236453042738258902158003155416615667").into_raw();
+    let shift = (I256::unchecked_from(195) - k).as_usize();
+    I256::from_raw(r.wrapping_mul(scale) >> shift)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn fixed_point_math() {
+        let wad = I256::unchecked_from(WAD);
+        assert_eq!(ln_wad(wad), I256::ZERO);
+        assert_eq!(
+            ln_wad(wad * I256::unchecked_from(2)),
+            int("693147180559945309")
+        );
+        assert_eq!(exp_wad(I256::ZERO), wad);
+        assert_eq!(exp_wad(wad), int("2718281828459045235"));
+        assert_eq!(
+            exp_wad(-wad * I256::unchecked_from(2)),
+            int("135335283236612691")
+        );
+        assert_eq!(exp_wad(int("-42139678854452767551")), I256::ZERO);
+    }
+
+    #[test]
+    fn required_bond_curve() {
+        // The bond curve of a game with the mainnet max game depth of 73. The bond at the root
+        // is `200 gwei * 400_000`, and the bond at the max depth approaches
+        // `200 gwei * 300_000_000`.
+        let bonds = [
+            (0, 80_000_000_000_000_000u128),
+            (1, 87_594_000_000_000_000),
+            (2, 95_908_800_000_000_000),
+            (10, 198_122_400_000_000_000),
+            (30, 1_215_127_800_000_000_000),
+            (72, 54_798_270_200_000_000_000),
+            (73, 59_999_999_800_000_000_000),
+        ];
+        for (depth, bond) in bonds {
+            assert_eq!(
+                required_bond(compute_gindex(depth, 0), 73).unwrap(),
+                U128::from(bond)
+            );
+        }
+
+        // The bond only depends on the depth of the position.
+        assert_eq!(
+            required_bond(Position::from(2), 4).unwrap(),
+            required_bond(Position::from(3), 4).unwrap()
+        );
+        assert_eq!(
+            required_bond(Position::from(2), 4).unwrap(),
+            U128::from(418_654_000_000_000_000u128)
+        );
+
+        assert_eq!(
+            required_bond(Position::from(32), 4)
+                .unwrap_err()
+                .to_string(),
+            "Game depth exceeded at position 32"
+        );
+        assert_eq!(
+            required_bond(Position::from(1), 0).unwrap_err().to_string(),
+            "Max game depth must be non-zero"
+        );
+    }
+
+    #[test]
+    fn affordable_depth_partial_tree() {
+        // Depths 1 through 3 cost 0.087594 + 0.0959088 + 0.105013 ether.
+        let through_3 = U128::from(288_515_800_000_000_000u128);
+        assert_eq!(affordable_depth(through_3, 73), 3);
+        assert_eq!(affordable_depth(through_3 - U128::from(1), 73), 2);
+        assert_eq!(
+            affordable_depth(U128::from(403_496_999_999_999_999u128), 73),
+            3
+        );
+        assert_eq!(
+            affordable_depth(U128::from(403_497_000_000_000_000u128), 73),
+            4
+        );
+        assert_eq!(
+            affordable_depth(U128::from(87_593_999_999_999_999u128), 73),
+            0
+        );
+        assert_eq!(affordable_depth(U128::MAX, 73), 73);
+    }
+}
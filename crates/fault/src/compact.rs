@@ -0,0 +1,254 @@
+//! This module contains the [CompactFaultDisputeState], a memory-efficient representation of a
+//! [FaultDisputeState] for monitoring many large games.
+
+use crate::{
+    compute_gindex, ClaimDag, ClaimData, Clock, FaultDisputeGame, FaultDisputeState, Gindex,
+};
+use alloy_primitives::Address;
+use durin_primitives::{Claim, DisputeGame, GameStatus};
+use std::{collections::HashMap, convert::TryFrom, hash::Hash, mem::size_of};
+
+/// An [Interner] stores each distinct value once, referring to it by a `u32` handle.
+#[derive(Debug, Clone, Default)]
+struct Interner<K> {
+    /// The distinct values, indexed by handle.
+    values: Vec<K>,
+    /// The handle of each distinct value.
+    handles: HashMap<K, u32>,
+}
+
+impl<K: Copy + Eq + Hash> Interner<K> {
+    /// Returns the handle of the value, interning it if it has not been seen before.
+    fn intern(&mut self, value: K) -> u32 {
+        let values = &mut self.values;
+        *self.handles.entry(value).or_insert_with(|| {
+            values.push(value);
+            (values.len() - 1) as u32
+        })
+    }
+
+    /// Returns the value referred to by the handle.
+    fn get(&self, handle: u32) -> K {
+        self.values[handle as usize]
+    }
+
+    /// Returns the approximate number of bytes held by the interner.
+    fn footprint(&self) -> usize {
+        self.values.capacity() * size_of::<K>()
+            + self.handles.capacity() * (size_of::<K>() + size_of::<u32>())
+    }
+}
+
+/// The [CompactClaimData] struct is a packed representation of a [ClaimData]. Positions are
+/// stored as their depth and index at depth, and claim values and addresses are stored as
+/// handles into the arenas of the owning [CompactFaultDisputeState].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompactClaimData {
+    parent_index: u32,
+    value: u32,
+    claimant: u32,
+    countered_by: u32,
+    index_at_depth: u64,
+    depth: u8,
+    visited: bool,
+    clock: Clock,
+}
+
+/// The [CompactFaultDisputeState] struct holds the same claims as a [FaultDisputeState], but
+/// interns claim values and addresses so that each distinct value is stored once. Griefed
+/// games repeat the same few values across many claims, so this significantly reduces the
+/// memory required to track them.
+///
+/// The compact state is read through the [ClaimDag] trait, and can be expanded back into a
+/// [FaultDisputeState] to be solved.
+#[derive(Debug, Clone)]
+pub struct CompactFaultDisputeState {
+    /// The packed claims of the state DAG.
+    claims: Vec<CompactClaimData>,
+    /// The arena of distinct claim values.
+    values: Interner<Claim>,
+    /// The arena of distinct claimant addresses.
+    addresses: Interner<Address>,
+    /// The root claim of the game.
+    root_claim: Claim,
+    /// The status of the dispute game.
+    status: GameStatus,
+    /// The max depth of the position tree.
+    pub max_depth: u8,
+    /// The duration of the game in seconds.
+    pub game_duration: u64,
+}
+
+impl CompactFaultDisputeState {
+    pub fn new(root_claim: Claim, status: GameStatus, max_depth: u8, game_duration: u64) -> Self {
+        Self {
+            claims: Vec::new(),
+            values: Interner::default(),
+            addresses: Interner::default(),
+            root_claim,
+            status,
+            max_depth,
+            game_duration,
+        }
+    }
+
+    /// Appends a claim to the state DAG.
+    ///
+    /// ### Takes
+    /// - `claim`: The claim to append.
+    ///
+    /// ### Returns
+    /// - `()` or [Err]: An error if the index at depth of the claim's position does not fit
+    ///   within a [u64], in which case the claim is not appended.
+    pub fn push(&mut self, claim: ClaimData) -> anyhow::Result<()> {
+        let index_at_depth = claim.position.index_at_depth()?;
+        let compact = CompactClaimData {
+            parent_index: claim.parent_index,
+            value: self.values.intern(claim.value),
+            claimant: self.addresses.intern(claim.claimant),
+            countered_by: self.addresses.intern(claim.countered_by),
+            index_at_depth,
+            depth: claim.position.depth(),
+            visited: claim.visited,
+            clock: claim.clock,
+        };
+        self.claims.push(compact);
+        Ok(())
+    }
+
+    /// Expands the compact state into a [FaultDisputeState]. Resolved subgames are not
+    /// tracked by the compact state, and are not carried over.
+    pub fn to_state(&self) -> FaultDisputeState {
+        let claims = (0..self.claims.len())
+            .filter_map(|i| self.claim(i))
+            .collect();
+        let mut state =
+            FaultDisputeState::new(claims, self.root_claim, self.status.clone(), self.max_depth);
+        state.game_duration = self.game_duration;
+        state
+    }
+
+    /// Returns the approximate number of bytes held by the state's claims and arenas.
+    pub fn footprint(&self) -> usize {
+        self.claims.capacity() * size_of::<CompactClaimData>()
+            + self.values.footprint()
+            + self.addresses.footprint()
+    }
+}
+
+impl TryFrom<&FaultDisputeState> for CompactFaultDisputeState {
+    type Error = anyhow::Error;
+
+    fn try_from(state: &FaultDisputeState) -> anyhow::Result<Self> {
+        let mut compact = Self::new(
+            state.root_claim(),
+            state.status().clone(),
+            state.max_depth,
+            state.game_duration,
+        );
+        state.state().iter().try_for_each(|c| compact.push(*c))?;
+        Ok(compact)
+    }
+}
+
+impl ClaimDag for CompactFaultDisputeState {
+    fn len(&self) -> usize {
+        self.claims.len()
+    }
+
+    fn claim(&self, index: usize) -> Option<ClaimData> {
+        let claim = self.claims.get(index)?;
+        Some(ClaimData {
+            parent_index: claim.parent_index,
+            visited: claim.visited,
+            value: self.values.get(claim.value),
+            position: compute_gindex(claim.depth, claim.index_at_depth),
+            clock: claim.clock,
+            claimant: self.addresses.get(claim.claimant),
+            countered_by: self.addresses.get(claim.countered_by),
+        })
+    }
+
+    fn max_depth(&self) -> u8 {
+        self.max_depth
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{AlphaClaimSolver, AlphabetTraceProvider, FaultDisputeSolver, Position};
+
+    /// A griefed game, where a few claimants repeat the same few values in long chains of
+    /// attacks.
+    fn griefed_state(claims: usize) -> FaultDisputeState {
+        let mut state = FaultDisputeState::new(
+            Vec::new(),
+            Claim::repeat_byte(0xFF),
+            GameStatus::InProgress,
+            63,
+        );
+        for i in 0..claims {
+            let (parent_index, position) = match i {
+                0 => (u32::MAX, Position::from(1)),
+                _ if i % 63 == 0 => (0, Position::from(2)),
+                _ => (i as u32 - 1, state.state()[i - 1].position.make_move(true)),
+            };
+            state.state_mut().push(ClaimData {
+                parent_index,
+                visited: i % 2 == 0,
+                value: Claim::repeat_byte((i % 4) as u8),
+                position,
+                clock: ((i as u128) << 64) | i as u128,
+                claimant: Address::with_last_byte((i % 3) as u8 + 1),
+                countered_by: Address::ZERO,
+            });
+        }
+        state
+    }
+
+    #[test]
+    fn compact_round_trip() {
+        let state = griefed_state(256);
+        let compact = CompactFaultDisputeState::try_from(&state).unwrap();
+
+        assert_eq!(compact.len(), state.len());
+        for i in 0..state.len() {
+            assert_eq!(compact.claim(i), state.claim(i));
+            assert_eq!(
+                compact.is_structural_counter(i),
+                state.is_structural_counter(i)
+            );
+        }
+        assert!(compact.claim(state.len()).is_none());
+
+        let expanded = compact.to_state();
+        assert_eq!(expanded.state(), state.state());
+        assert_eq!(expanded.root_claim(), state.root_claim());
+        assert_eq!(expanded.max_depth, state.max_depth);
+    }
+
+    #[test]
+    fn compact_required_positions() {
+        let state = griefed_state(256);
+        let solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 63)));
+
+        assert_eq!(
+            solver.required_positions(&CompactFaultDisputeState::try_from(&state).unwrap()),
+            solver.required_positions(&state)
+        );
+    }
+
+    #[test]
+    fn compact_memory() {
+        let claims = 50_000;
+        let state = griefed_state(claims);
+        let mut compact = CompactFaultDisputeState::try_from(&state).unwrap();
+        compact.claims.shrink_to_fit();
+
+        let full_footprint = state.state().capacity() * size_of::<ClaimData>();
+        assert!(size_of::<CompactClaimData>() * 2 <= size_of::<ClaimData>());
+        assert!(compact.footprint() * 2 <= full_footprint);
+    }
+}
@@ -0,0 +1,462 @@
+//! This module contains the [MoveDispatcher] trait, which closes the loop between the
+//! [FaultSolverResponse]s computed by a solver and the transactions that act on them, and the
+//! [TxBundler], which bundles them into signed transactions.
+
+use crate::{required_bond, FaultDisputeGame, FaultDisputeState, FaultSolverResponse, Gindex};
+use alloy_primitives::{keccak256, Address, Bytes, TxHash, U256};
+use alloy_sol_types::{sol, SolCall};
+use std::{collections::BTreeMap, convert::TryFrom};
+
+sol! {
+    /// Attacks the claim at `_parentIndex` in the `FaultDisputeGame` contract.
+    function attack(uint256 _parentIndex, bytes32 _claim) external payable;
+
+    /// Defends the claim at `_parentIndex` in the `FaultDisputeGame` contract.
+    function defend(uint256 _parentIndex, bytes32 _claim) external payable;
+
+    /// Performs a VM step against the leaf claim at `_claimIndex` in the `FaultDisputeGame`
+    /// contract.
+    function step(uint256 _claimIndex, bool _isAttack, bytes _stateData, bytes _proof) external;
+
+    /// Resolves the subgame rooted at the claim at `_claimIndex` in the `FaultDisputeGame`
+    /// contract.
+    function resolveClaim(uint256 _claimIndex) external payable;
+
+    /// Resolves the `FaultDisputeGame` contract once the subgame of its root claim has been
+    /// resolved.
+    function resolve() external returns (uint8 status_);
+
+    /// Claims the credit owed to `_recipient` by the `FaultDisputeGame` contract once the game
+    /// has resolved.
+    function claimCredit(address _recipient) external;
+
+    /// Returns the number of claims in the state DAG of the `FaultDisputeGame` contract.
+    function claimDataLen() external view returns (uint256 len_);
+}
+
+/// Encodes the `FaultDisputeGame` calldata for the action described by a [FaultSolverResponse].
+///
+/// ### Takes
+/// - `response`: The [FaultSolverResponse] to encode.
+///
+/// ### Returns
+/// - The calldata of the move, step, or resolution, or [None] if the response requires no
+///   action.
+pub fn encode_response<T: AsRef<[u8]>>(response: &FaultSolverResponse<T>) -> Option<Bytes> {
+    let calldata = match response {
+        FaultSolverResponse::Move(true, parent_index, claim) => attackCall {
+            _parentIndex: U256::from(*parent_index),
+            _claim: *claim,
+        }
+        .abi_encode(),
+        FaultSolverResponse::Move(false, parent_index, claim) => defendCall {
+            _parentIndex: U256::from(*parent_index),
+            _claim: *claim,
+        }
+        .abi_encode(),
+        FaultSolverResponse::Step(is_attack, claim_index, state_data, proof) => stepCall {
+            _claimIndex: U256::from(*claim_index),
+            _isAttack: *is_attack,
+            _stateData: state_data.as_ref().as_ref().to_vec(),
+            _proof: proof.to_vec(),
+        }
+        .abi_encode(),
+        FaultSolverResponse::Resolve(claim_index) => resolve_claim_calldata(*claim_index),
+        FaultSolverResponse::Skip(_) | FaultSolverResponse::Defer(_) => return None,
+    };
+    Some(calldata.into())
+}
+
+/// Encodes the `FaultDisputeGame` calldata to resolve the subgame rooted at a claim.
+pub(crate) fn resolve_claim_calldata(claim_index: usize) -> Vec<u8> {
+    resolveClaimCall {
+        _claimIndex: U256::from(claim_index),
+    }
+    .abi_encode()
+}
+
+/// Encodes the `FaultDisputeGame` calldata to resolve the game.
+pub(crate) fn resolve_calldata() -> Vec<u8> {
+    resolveCall {}.abi_encode()
+}
+
+/// Encodes the `FaultDisputeGame` calldata to claim the credit owed to a recipient.
+///
+/// ### Takes
+/// - `recipient`: The address to claim the credit of.
+///
+/// ### Returns
+/// - The calldata of the `claimCredit` call.
+pub fn claim_credit_calldata(recipient: Address) -> Vec<u8> {
+    claimCreditCall {
+        _recipient: recipient,
+    }
+    .abi_encode()
+}
+
+/// Encodes the `FaultDisputeGame` calldata to fetch the number of claims in the state DAG,
+/// for polling whether a local state needs a reload.
+///
+/// ### Returns
+/// - The calldata of the `claimDataLen` call.
+pub fn claim_data_len_calldata() -> Vec<u8> {
+    claimDataLenCall {}.abi_encode()
+}
+
+/// Decodes the return data of a `claimDataLen` call.
+///
+/// ### Takes
+/// - `data`: The return data of the call.
+///
+/// ### Returns
+/// - `usize` or [Err]: The number of claims in the state DAG, or an error if the return data
+///   is malformed or the count does not fit in a `usize`.
+pub fn decode_claim_data_len(data: &[u8]) -> anyhow::Result<usize> {
+    let len = claimDataLenCall::abi_decode_returns(data, true)
+        .map_err(|e| anyhow::anyhow!("Failed to decode claimDataLen return data: {}", e))?
+        .len_;
+    usize::try_from(len).map_err(|_| anyhow::anyhow!("Claim count {} does not fit in a usize", len))
+}
+
+/// Encodes one `claimCredit` call for each recipient that is owed a positive credit, in order
+/// of recipient address.
+///
+/// ### Takes
+/// - `credits`: The credit owed to each recipient after the game has resolved.
+///
+/// ### Returns
+/// - The recipients owed a positive credit and the calldata to claim it.
+pub fn claim_credit_batch(credits: &BTreeMap<Address, U256>) -> Vec<(Address, Vec<u8>)> {
+    credits
+        .iter()
+        .filter(|(_, credit)| **credit > U256::ZERO)
+        .map(|(recipient, _)| (*recipient, claim_credit_calldata(*recipient)))
+        .collect()
+}
+
+/// A [MoveDispatcher] submits the action described by a [FaultSolverResponse] against the
+/// `FaultDisputeGame` contract.
+pub trait MoveDispatcher<T: AsRef<[u8]>> {
+    /// Dispatches the action described by the response.
+    ///
+    /// ### Takes
+    /// - `response`: The [FaultSolverResponse] to act on.
+    ///
+    /// ### Returns
+    /// - [TxHash] or [Err]: The hash of the submitted transaction, or an error if the
+    ///   response requires no action or could not be dispatched.
+    fn dispatch(&self, response: &FaultSolverResponse<T>) -> anyhow::Result<TxHash>;
+}
+
+/// The [LoggingDispatcher] is a [MoveDispatcher] for dry runs. It logs the calldata of each
+/// action rather than submitting it, and returns the hash of the calldata in place of a
+/// transaction hash.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct LoggingDispatcher;
+
+impl<T: AsRef<[u8]>> MoveDispatcher<T> for LoggingDispatcher {
+    fn dispatch(&self, response: &FaultSolverResponse<T>) -> anyhow::Result<TxHash> {
+        let claim_index = response.claim_index();
+        let calldata = encode_response(response).ok_or(anyhow::anyhow!(
+            "Response against claim {} has no action to dispatch",
+            claim_index
+        ))?;
+
+        tracing::info!(claim_index, %calldata, "Dry run dispatch");
+        Ok(keccak256(&calldata))
+    }
+}
+
+/// An [UnsignedTransaction] is an EIP-1559 transaction against the `FaultDisputeGame`
+/// contract, prepared by a [TxBundler] and awaiting a signature.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnsignedTransaction {
+    /// The chain ID that the transaction is valid on.
+    pub chain_id: u64,
+    /// The nonce of the sender.
+    pub nonce: u64,
+    /// The address of the `FaultDisputeGame` contract.
+    pub to: Address,
+    /// The value sent with the transaction, i.e. the bond of a move.
+    pub value: U256,
+    /// The gas limit of the transaction.
+    pub gas_limit: u64,
+    /// The maximum total fee per unit of gas.
+    pub max_fee_per_gas: u128,
+    /// The maximum priority fee per unit of gas.
+    pub max_priority_fee_per_gas: u128,
+    /// The calldata of the transaction.
+    pub input: Bytes,
+}
+
+/// A [SignedTransaction] is an [UnsignedTransaction] along with its signed, encoded envelope,
+/// ready to be submitted to a relay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignedTransaction {
+    /// The transaction that was signed.
+    pub tx: UnsignedTransaction,
+    /// The raw signed transaction envelope.
+    pub raw: Bytes,
+}
+
+/// A [TxSigner] signs the transactions prepared by a [TxBundler] on behalf of the sender.
+pub trait TxSigner {
+    /// Signs the transaction.
+    ///
+    /// ### Takes
+    /// - `tx`: The [UnsignedTransaction] to sign.
+    ///
+    /// ### Returns
+    /// - [Bytes] or [Err]: The raw signed transaction envelope.
+    fn sign(&self, tx: &UnsignedTransaction) -> anyhow::Result<Bytes>;
+}
+
+/// The [TxBundler] turns the [FaultSolverResponse]s of a solver into a bundle of signed
+/// transactions with consecutive nonces, for submission to a private relay.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxBundler {
+    /// The address of the `FaultDisputeGame` contract.
+    pub game: Address,
+    /// The chain ID that the transactions are valid on.
+    pub chain_id: u64,
+    /// The nonce of the next transaction. It is advanced past each bundle that is produced,
+    /// and may be set directly to resynchronize it with the chain.
+    pub nonce: u64,
+    /// The gas limit of each transaction.
+    pub gas_limit: u64,
+    /// The maximum total fee per unit of gas.
+    pub max_fee_per_gas: u128,
+    /// The maximum priority fee per unit of gas.
+    pub max_priority_fee_per_gas: u128,
+}
+
+impl TxBundler {
+    /// Bundles the actions described by the responses into signed transactions, in order.
+    /// Responses that require no action are left out of the bundle, and moves carry the
+    /// bond required at the position they move to, as computed by [required_bond].
+    ///
+    /// ### Takes
+    /// - `world`: The [FaultDisputeState] that the responses were computed against.
+    /// - `responses`: The [FaultSolverResponse]s to bundle.
+    /// - `signer`: The [TxSigner] to sign each transaction with.
+    ///
+    /// ### Returns
+    /// - [`Vec<SignedTransaction>`] or [Err]: The signed transactions, or an error if a move
+    ///   is against a missing claim or beyond the max depth, or a transaction could not be
+    ///   signed. The nonce is only advanced if the whole bundle is produced.
+    pub fn bundle<T: AsRef<[u8]>>(
+        &mut self,
+        world: &FaultDisputeState,
+        responses: &[FaultSolverResponse<T>],
+        signer: &impl TxSigner,
+    ) -> anyhow::Result<Vec<SignedTransaction>> {
+        let mut nonce = self.nonce;
+        let mut bundle = Vec::new();
+        for response in responses {
+            let Some(input) = encode_response(response) else {
+                continue;
+            };
+            let value = match response {
+                FaultSolverResponse::Move(is_attack, parent_index, _) => {
+                    let parent = world.state().get(*parent_index).ok_or(anyhow::anyhow!(
+                        "Move against missing claim {}",
+                        parent_index
+                    ))?;
+                    let position = parent.position.make_move(*is_attack);
+                    U256::from(required_bond(position, world.max_depth)?)
+                }
+                _ => U256::ZERO,
+            };
+
+            let tx = UnsignedTransaction {
+                chain_id: self.chain_id,
+                nonce,
+                to: self.game,
+                value,
+                gas_limit: self.gas_limit,
+                max_fee_per_gas: self.max_fee_per_gas,
+                max_priority_fee_per_gas: self.max_priority_fee_per_gas,
+                input,
+            };
+            let raw = signer.sign(&tx)?;
+            bundle.push(SignedTransaction { tx, raw });
+            nonce += 1;
+        }
+
+        self.nonce = nonce;
+        Ok(bundle)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{state::alphabet_claim, Position};
+    use durin_primitives::{Claim, GameStatus};
+    use std::sync::Arc;
+
+    #[test]
+    fn encode_moves() {
+        let claim = Claim::repeat_byte(0xab);
+
+        let attack =
+            encode_response::<[u8; 1]>(&FaultSolverResponse::Move(true, 3, claim)).unwrap();
+        let decoded = attackCall::abi_decode(&attack, true).unwrap();
+        assert_eq!(decoded._parentIndex, U256::from(3));
+        assert_eq!(decoded._claim, claim);
+
+        let defend =
+            encode_response::<[u8; 1]>(&FaultSolverResponse::Move(false, 4, claim)).unwrap();
+        let decoded = defendCall::abi_decode(&defend, true).unwrap();
+        assert_eq!(decoded._parentIndex, U256::from(4));
+        assert_eq!(decoded._claim, claim);
+    }
+
+    #[test]
+    fn encode_step() {
+        let step = encode_response(&FaultSolverResponse::Step(
+            true,
+            7,
+            Arc::new([b'a']),
+            Arc::from([1u8, 2, 3]),
+        ))
+        .unwrap();
+        let decoded = stepCall::abi_decode(&step, true).unwrap();
+        assert_eq!(decoded._claimIndex, U256::from(7));
+        assert!(decoded._isAttack);
+        assert_eq!(decoded._stateData, vec![b'a']);
+        assert_eq!(decoded._proof, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn encode_resolve() {
+        let resolve = encode_response::<[u8; 1]>(&FaultSolverResponse::Resolve(5)).unwrap();
+        let decoded = resolveClaimCall::abi_decode(&resolve, true).unwrap();
+        assert_eq!(decoded._claimIndex, U256::from(5));
+    }
+
+    #[test]
+    fn encode_claim_credit() {
+        let recipient = Address::repeat_byte(0x11);
+        let calldata = claim_credit_calldata(recipient);
+        assert_eq!(calldata[..4], keccak256("claimCredit(address)")[..4]);
+        assert_eq!(calldata[4..16], [0u8; 12]);
+        assert_eq!(calldata[16..], recipient[..]);
+        assert_eq!(
+            claimCreditCall::abi_decode(&calldata, true)
+                .unwrap()
+                ._recipient,
+            recipient
+        );
+
+        let credits = BTreeMap::from([
+            (Address::repeat_byte(0x22), U256::ZERO),
+            (recipient, U256::from(1)),
+        ]);
+        assert_eq!(claim_credit_batch(&credits), vec![(recipient, calldata)]);
+    }
+
+    #[test]
+    fn claim_data_len() {
+        let calldata = claim_data_len_calldata();
+        assert_eq!(calldata, keccak256("claimDataLen()")[..4]);
+
+        let data = U256::from(42).to_be_bytes::<32>();
+        assert_eq!(decode_claim_data_len(&data).unwrap(), 42);
+        assert!(decode_claim_data_len(&data[..31]).is_err());
+    }
+
+    #[test]
+    fn logging_dispatcher() {
+        let response = FaultSolverResponse::<[u8; 1]>::Move(true, 0, Claim::ZERO);
+        assert_eq!(
+            LoggingDispatcher.dispatch(&response).unwrap(),
+            keccak256(encode_response(&response).unwrap())
+        );
+
+        assert!(encode_response::<[u8; 1]>(&FaultSolverResponse::Skip(1)).is_none());
+        assert_eq!(
+            LoggingDispatcher
+                .dispatch(&FaultSolverResponse::<[u8; 1]>::Defer(1))
+                .unwrap_err()
+                .to_string(),
+            "Response against claim 1 has no action to dispatch"
+        );
+    }
+
+    /// A [TxSigner] that "signs" a transaction by prefixing its calldata with its nonce.
+    struct MockSigner;
+
+    impl TxSigner for MockSigner {
+        fn sign(&self, tx: &UnsignedTransaction) -> anyhow::Result<Bytes> {
+            if tx.nonce > 10 {
+                anyhow::bail!("Nonce {} is too high", tx.nonce);
+            }
+            Ok([&[tx.nonce as u8], tx.input.as_ref()].concat().into())
+        }
+    }
+
+    #[test]
+    fn bundle_responses() {
+        let claim = |parent_index, position| alphabet_claim(parent_index, position, Claim::ZERO);
+        let state = FaultDisputeState::new(
+            vec![
+                claim(u32::MAX, Position::from(1)),
+                claim(0, Position::from(2)),
+            ],
+            Claim::ZERO,
+            GameStatus::InProgress,
+            4,
+        );
+        let mut bundler = TxBundler {
+            game: Address::repeat_byte(0x11),
+            chain_id: 1,
+            nonce: 7,
+            gas_limit: 1_000_000,
+            max_fee_per_gas: 100,
+            max_priority_fee_per_gas: 2,
+        };
+
+        let responses = vec![
+            FaultSolverResponse::Move(true, 1, Claim::repeat_byte(0xab)),
+            FaultSolverResponse::Skip(0),
+            FaultSolverResponse::Step(false, 1, Arc::new([b'a']), Arc::from([1u8])),
+        ];
+        let bundle = bundler.bundle(&state, &responses, &MockSigner).unwrap();
+        assert_eq!(bundle.len(), 2);
+        assert_eq!(bundler.nonce, 9);
+
+        let attack = attackCall::abi_decode(&bundle[0].tx.input, true).unwrap();
+        assert_eq!(attack._parentIndex, U256::from(1));
+        assert_eq!(attack._claim, Claim::repeat_byte(0xab));
+        assert_eq!(bundle[0].tx.nonce, 7);
+        assert_eq!(
+            bundle[0].tx.value,
+            U256::from(2_190_890_200_000_000_000u128)
+        );
+        assert_eq!(bundle[0].tx.to, Address::repeat_byte(0x11));
+        assert_eq!(bundle[0].raw[0], 7);
+        assert_eq!(bundle[0].raw[1..], bundle[0].tx.input[..]);
+
+        let step = stepCall::abi_decode(&bundle[1].tx.input, true).unwrap();
+        assert_eq!(step._claimIndex, U256::from(1));
+        assert!(!step._isAttack);
+        assert_eq!(bundle[1].tx.nonce, 8);
+        assert_eq!(bundle[1].tx.value, U256::ZERO);
+
+        // A failed bundle does not advance the nonce.
+        bundler.nonce = 10;
+        assert_eq!(
+            bundler
+                .bundle(
+                    &state,
+                    &[responses[0].clone(), responses[0].clone()],
+                    &MockSigner
+                )
+                .unwrap_err()
+                .to_string(),
+            "Nonce 11 is too high"
+        );
+        assert_eq!(bundler.nonce, 10);
+    }
+}
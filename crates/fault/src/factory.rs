@@ -0,0 +1,119 @@
+//! This module contains the [GameFactory] trait, which serves the games created by the
+//! `DisputeGameFactory` contract, and [enumerate_games], the entry point for discovering the
+//! games to monitor.
+
+use alloy_primitives::Address;
+use durin_primitives::{Claim, GameType};
+use std::ops::Range;
+
+/// A game created by the `DisputeGameFactory`: its [GameType], root claim, and the address of
+/// its proxy.
+pub type FactoryGame = (GameType, Claim, Address);
+
+/// A [GameFactory] serves the games created by the `DisputeGameFactory` contract, indexed in
+/// order of creation.
+pub trait GameFactory {
+    /// Returns the number of games created by the factory, as returned by `gameCount()`.
+    fn game_count(&self) -> anyhow::Result<u64>;
+
+    /// Returns the games at the given range of indices, as returned by `gameAtIndex(i)` along
+    /// with the root claim of each game's proxy.
+    ///
+    /// ### Takes
+    /// - `indices`: The indices of the games, which are all less than the game count.
+    ///
+    /// ### Returns
+    /// - The games at the indices, in order, or an error if they could not be fetched.
+    fn games(&self, indices: Range<u64>) -> anyhow::Result<Vec<FactoryGame>>;
+}
+
+/// Enumerates the games created by a [GameFactory], fetching them in pages.
+///
+/// ### Takes
+/// - `factory`: The [GameFactory] to enumerate.
+/// - `game_type`: The [GameType] of the games to return, or [None] to return every game.
+/// - `page_size`: The maximum number of games to fetch from the factory at once.
+///
+/// ### Returns
+/// - The games created by the factory in order of creation, or an error if the page size is
+///   zero or the factory fails.
+pub fn enumerate_games<F: GameFactory>(
+    factory: &F,
+    game_type: Option<GameType>,
+    page_size: u64,
+) -> anyhow::Result<Vec<FactoryGame>> {
+    if page_size == 0 {
+        anyhow::bail!("Page size must be non-zero");
+    }
+
+    let count = factory.game_count()?;
+    let mut games = Vec::new();
+    let mut start = 0;
+    while start < count {
+        let end = count.min(start + page_size);
+        games.extend(
+            factory
+                .games(start..end)?
+                .into_iter()
+                .filter(|(t, _, _)| game_type.is_none_or(|game_type| *t == game_type)),
+        );
+        start = end;
+    }
+    Ok(games)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// A [GameFactory] serving a fixed list of games, recording the pages it serves.
+    struct MockFactory {
+        games: Vec<FactoryGame>,
+        pages: RefCell<Vec<Range<u64>>>,
+    }
+
+    impl GameFactory for MockFactory {
+        fn game_count(&self) -> anyhow::Result<u64> {
+            Ok(self.games.len() as u64)
+        }
+
+        fn games(&self, indices: Range<u64>) -> anyhow::Result<Vec<FactoryGame>> {
+            self.pages.borrow_mut().push(indices.clone());
+            Ok(self.games[indices.start as usize..indices.end as usize].to_vec())
+        }
+    }
+
+    #[test]
+    fn enumerate_games_paginated() {
+        let game = |game_type, byte| {
+            (
+                game_type,
+                Claim::repeat_byte(byte),
+                Address::repeat_byte(byte),
+            )
+        };
+        let factory = MockFactory {
+            games: vec![
+                game(GameType::FaultCannon, 0x01),
+                game(GameType::Alphabet, 0x02),
+                game(GameType::FaultCannon, 0x03),
+                game(GameType::Alphabet, 0x04),
+                game(GameType::FaultCannon, 0x05),
+            ],
+            pages: RefCell::new(Vec::new()),
+        };
+
+        assert_eq!(enumerate_games(&factory, None, 2).unwrap(), factory.games);
+        assert_eq!(*factory.pages.borrow(), vec![0..2, 2..4, 4..5]);
+
+        assert_eq!(
+            enumerate_games(&factory, Some(GameType::Alphabet), 10).unwrap(),
+            vec![factory.games[1], factory.games[3]]
+        );
+        assert_eq!(
+            enumerate_games(&factory, None, 0).unwrap_err().to_string(),
+            "Page size must be non-zero"
+        );
+    }
+}
@@ -3,6 +3,9 @@
 extern crate alloy_primitives;
 extern crate alloy_sol_types;
 extern crate durin_primitives;
+extern crate serde;
+extern crate serde_json;
+extern crate tracing;
 
 #[cfg(test)]
 extern crate proptest;
@@ -10,16 +13,50 @@ extern crate proptest;
 mod types;
 pub use types::*;
 
+mod bond;
+pub use bond::{affordable_depth, required_bond};
+
 mod providers;
+pub use providers::*;
 
 mod state;
-pub use state::{ClaimData, FaultDisputeState};
+pub use state::{
+    ClaimData, DivergenceSummary, FaultDisputeState, StateDiff, StepBundle, TreeView,
+    DEFAULT_GAME_DURATION,
+};
 
 mod traits;
 pub use traits::*;
 
+pub mod rules;
+
 mod solver;
 pub use solver::*;
 
 mod solvers;
 pub use solvers::*;
+
+mod multi;
+pub use multi::*;
+
+mod dispatch;
+pub use dispatch::{
+    claim_credit_batch, claim_credit_calldata, claim_data_len_calldata, decode_claim_data_len,
+    encode_response, LoggingDispatcher, MoveDispatcher, SignedTransaction, TxBundler, TxSigner,
+    UnsignedTransaction,
+};
+
+mod factory;
+pub use factory::{enumerate_games, FactoryGame, GameFactory};
+
+mod monitor;
+pub use monitor::{ClaimRateMonitor, RateAlert};
+
+mod schedule;
+pub use schedule::MoveScheduler;
+
+#[cfg(test)]
+mod runner;
+
+mod compact;
+pub use compact::{CompactClaimData, CompactFaultDisputeState};
@@ -0,0 +1,134 @@
+//! This module contains the [ClaimRateMonitor], which watches successive snapshots of a
+//! [FaultDisputeState] for floods of claims that may indicate a griefing attack in progress.
+
+use crate::{FaultDisputeGame, FaultDisputeState, Gindex};
+use std::collections::BTreeMap;
+
+/// A [RateAlert] is raised by a [ClaimRateMonitor] when claims are added at a single depth of
+/// the position tree faster than the monitor's threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RateAlert {
+    /// The depth at which the claims were added.
+    pub depth: u8,
+    /// The number of claims added at the depth since the previous snapshot.
+    pub claims: usize,
+    /// The number of seconds elapsed since the previous snapshot.
+    pub elapsed: u64,
+    /// The rate at which claims were added at the depth, in claims per second.
+    pub rate: f64,
+}
+
+/// The [ClaimRateMonitor] computes the rate at which claims are added at each depth of the
+/// position tree between successive snapshots of a [FaultDisputeState], and raises a
+/// [RateAlert] for each depth where the rate exceeds the threshold.
+#[derive(Debug, Clone)]
+pub struct ClaimRateMonitor {
+    /// The maximum number of claims per second allowed at a single depth before an alert is
+    /// raised.
+    pub threshold: f64,
+    /// The number of claims in the previously observed snapshot and the timestamp at which it
+    /// was taken.
+    previous: Option<(usize, u64)>,
+}
+
+impl ClaimRateMonitor {
+    pub fn new(threshold: f64) -> Self {
+        Self {
+            threshold,
+            previous: None,
+        }
+    }
+
+    /// Observes a new snapshot of the game, counting the claims appended since the previously
+    /// observed snapshot. Claims are only ever appended to a game, so the added claims are
+    /// those past the previous snapshot's claim count. Snapshots taken within the same second are
+    /// treated as one second apart, so that a burst is not hidden by a zero elapsed time.
+    ///
+    /// ### Takes
+    /// - `state`: The current snapshot of the game's state.
+    /// - `timestamp`: The timestamp at which the snapshot was taken, in seconds.
+    ///
+    /// ### Returns
+    /// - The [RateAlert]s for the depths where the rate of added claims exceeds the
+    ///   threshold, ordered by depth. The first snapshot observed never raises an alert.
+    pub fn observe(&mut self, state: &FaultDisputeState, timestamp: u64) -> Vec<RateAlert> {
+        let alerts = match &self.previous {
+            Some((previous_len, previous_timestamp)) => {
+                let elapsed = timestamp.saturating_sub(*previous_timestamp).max(1);
+
+                let mut added = BTreeMap::<u8, usize>::new();
+                state
+                    .state()
+                    .iter()
+                    .skip(*previous_len)
+                    .map(|claim| claim.position)
+                    .filter(|position| position.is_valid())
+                    .for_each(|position| *added.entry(position.depth()).or_default() += 1);
+
+                added
+                    .into_iter()
+                    .map(|(depth, claims)| RateAlert {
+                        depth,
+                        claims,
+                        elapsed,
+                        rate: claims as f64 / elapsed as f64,
+                    })
+                    .filter(|alert| alert.rate > self.threshold)
+                    .collect()
+            }
+            None => Vec::new(),
+        };
+
+        self.previous = Some((state.state().len(), timestamp));
+        alerts
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{state::alphabet_claim, ClaimData, Position};
+    use durin_primitives::{Claim, GameStatus};
+
+    fn claim(parent_index: u32, position: Position) -> ClaimData {
+        alphabet_claim(parent_index, position, Claim::ZERO)
+    }
+
+    #[test]
+    fn burst_raises_alert() {
+        let mut state = FaultDisputeState::new(
+            vec![claim(u32::MAX, Position::from(1))],
+            Claim::ZERO,
+            GameStatus::InProgress,
+            4,
+        );
+        let mut monitor = ClaimRateMonitor::new(1.0);
+        assert!(monitor.observe(&state, 100).is_empty());
+
+        // A burst of claims at depth 2 within ten seconds, alongside a steady trickle at
+        // depth 1.
+        state.state_mut().push(claim(0, Position::from(2)));
+        for _ in 0..15 {
+            state.state_mut().push(claim(1, Position::from(4)));
+        }
+        assert_eq!(
+            monitor.observe(&state, 110),
+            vec![RateAlert {
+                depth: 2,
+                claims: 15,
+                elapsed: 10,
+                rate: 1.5,
+            }]
+        );
+
+        // Once the burst is over, the monitor stops raising alerts.
+        state.state_mut().push(claim(1, Position::from(5)));
+        assert!(monitor.observe(&state, 120).is_empty());
+
+        // Claims at the zero position are not at any depth, and are not counted.
+        for _ in 0..15 {
+            state.state_mut().push(claim(0, Position::from(0)));
+        }
+        assert!(monitor.observe(&state, 121).is_empty());
+    }
+}
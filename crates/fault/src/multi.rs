@@ -0,0 +1,146 @@
+//! This module contains the [MultiGameSolver], which solves many [FaultDisputeState]s with a
+//! shared [FaultDisputeSolver].
+
+use crate::{
+    FaultClaimSolver, FaultDisputeSolver, FaultDisputeState, FaultSolverResponse, TraceProvider,
+};
+use durin_primitives::DisputeSolver;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+};
+
+/// The identifier of a game within a [MultiGameSolver]; its index in [MultiGameSolver::games].
+pub type GameId = usize;
+
+/// The responses for a single game solved by a [MultiGameSolver].
+pub type GameResponses<T> = (GameId, anyhow::Result<Arc<[FaultSolverResponse<T>]>>);
+
+/// A [MultiGameSolver] solves many [FaultDisputeState]s with a single [FaultDisputeSolver],
+/// sharing its [TraceProvider] across all games. Games are solved concurrently, with at most
+/// `concurrency` games being solved at once.
+pub struct MultiGameSolver<T, P, S>
+where
+    T: AsRef<[u8]>,
+    P: TraceProvider<T>,
+    S: FaultClaimSolver<T, P>,
+{
+    /// The solver shared by all games.
+    pub solver: FaultDisputeSolver<T, P, S>,
+    /// The games being solved.
+    pub games: Vec<Arc<Mutex<FaultDisputeState>>>,
+    /// The maximum number of games solved at once.
+    pub concurrency: usize,
+}
+
+impl<T, P, S> MultiGameSolver<T, P, S>
+where
+    T: AsRef<[u8]> + Send + Sync,
+    P: TraceProvider<T> + Sync,
+    S: FaultClaimSolver<T, P> + Sync,
+{
+    pub fn new(
+        solver: FaultDisputeSolver<T, P, S>,
+        games: Vec<Arc<Mutex<FaultDisputeState>>>,
+        concurrency: usize,
+    ) -> Self {
+        Self {
+            solver,
+            games,
+            concurrency,
+        }
+    }
+
+    /// Computes the available moves for every game. A failure to solve one game does not
+    /// prevent the other games from being solved.
+    ///
+    /// ### Returns
+    /// - The responses for each game, ordered by [GameId].
+    pub fn available_moves_all(&self) -> Vec<GameResponses<T>> {
+        let next_game = AtomicUsize::new(0);
+        let results = Mutex::new(Vec::with_capacity(self.games.len()));
+
+        thread::scope(|scope| {
+            for _ in 0..self.concurrency.clamp(1, self.games.len().max(1)) {
+                scope.spawn(|| loop {
+                    let game_id = next_game.fetch_add(1, Ordering::Relaxed);
+                    let Some(game) = self.games.get(game_id) else {
+                        break;
+                    };
+
+                    let responses = game
+                        .lock()
+                        .map_err(|_| anyhow::anyhow!("Game state lock poisoned"))
+                        .and_then(|mut game| self.solver.available_moves(&mut game));
+                    if let Ok(mut results) = results.lock() {
+                        results.push((game_id, responses));
+                    }
+                });
+            }
+        });
+
+        let mut results = results.into_inner().unwrap_or_default();
+        results.sort_unstable_by_key(|(game_id, _)| *game_id);
+        results
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{AlphaClaimSolver, AlphabetTraceProvider, ClaimData, FaultDisputeGame, Position};
+    use alloy_primitives::Address;
+    use durin_primitives::{Claim, GameStatus};
+
+    fn game(root_claim: Claim) -> FaultDisputeState {
+        FaultDisputeState::new(
+            vec![ClaimData {
+                parent_index: u32::MAX,
+                visited: false,
+                value: root_claim,
+                position: Position::from(1),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            }],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        )
+    }
+
+    #[test]
+    fn available_moves_all() {
+        let provider = AlphabetTraceProvider::new(b'a', 4);
+        let honest_root = provider.state_hash(Position::from(1)).unwrap();
+        let dishonest_root = Claim::repeat_byte(0xFF);
+        let attack = provider.state_hash(Position::from(2)).unwrap();
+        let mut solver = FaultDisputeSolver::new(AlphaClaimSolver::new(provider));
+
+        for concurrency in [0, 1, 2, 8] {
+            let games = [honest_root, dishonest_root, honest_root, dishonest_root]
+                .iter()
+                .map(|root_claim| Arc::new(Mutex::new(game(*root_claim))))
+                .collect::<Vec<_>>();
+            let multi = MultiGameSolver::new(solver, games, concurrency);
+
+            let results = multi.available_moves_all();
+            assert_eq!(results.len(), 4);
+            for (i, (game_id, responses)) in results.into_iter().enumerate() {
+                let expected = if i % 2 == 0 {
+                    FaultSolverResponse::Skip(0)
+                } else {
+                    FaultSolverResponse::Move(true, 0, attack)
+                };
+                assert_eq!(game_id, i);
+                assert_eq!(&[expected], responses.unwrap().as_ref());
+                assert!(multi.games[i].lock().unwrap().state()[0].visited);
+            }
+
+            solver = multi.solver;
+        }
+    }
+}
@@ -20,6 +20,12 @@ pub struct AlphabetTraceProvider {
     pub absolute_prestate: u8,
     /// The maximum depth of the dispute game position tree.
     pub max_depth: u8,
+    /// Whether or not the [VMStatus] is stamped into the first byte of the state hashes
+    /// served by the provider. When disabled, the raw state hashes are served.
+    pub stamp_vm_status: bool,
+    /// When set, the absolute prestate hash served by the provider in place of the hash of
+    /// the absolute prestate.
+    pub absolute_prestate_hash_override: Option<Claim>,
 }
 
 impl AlphabetTraceProvider {
@@ -27,6 +33,8 @@ impl AlphabetTraceProvider {
         Self {
             absolute_prestate,
             max_depth,
+            stamp_vm_status: true,
+            absolute_prestate_hash_override: None,
         }
     }
 }
@@ -37,15 +45,21 @@ impl TraceProvider<[u8; 1]> for AlphabetTraceProvider {
     }
 
     fn absolute_prestate_hash(&self) -> Claim {
+        if let Some(prestate_hash) = self.absolute_prestate_hash_override {
+            return prestate_hash;
+        }
+
         let prestate = U256::from(self.absolute_prestate);
         let mut prestate_hash = keccak256(<sol!(uint256)>::abi_encode(&prestate));
-        prestate_hash[0] = VMStatus::Unfinished as u8;
+        if self.stamp_vm_status {
+            prestate_hash[0] = VMStatus::Unfinished as u8;
+        }
         prestate_hash
     }
 
     fn state_at(&self, position: Position) -> anyhow::Result<Arc<[u8; 1]>> {
         let absolute_prestate = self.absolute_prestate as u64;
-        let trace_index = position.trace_index(self.max_depth);
+        let trace_index = position.trace_index(self.max_depth)?;
 
         let state = (absolute_prestate + trace_index + 1)
             .try_into()
@@ -55,17 +69,28 @@ impl TraceProvider<[u8; 1]> for AlphabetTraceProvider {
 
     fn state_hash(&self, position: Position) -> anyhow::Result<Claim> {
         let state_sol = (
-            U256::from(position.trace_index(self.max_depth)),
+            U256::from(position.trace_index(self.max_depth)?),
             U256::from(self.state_at(position)?[0]),
         );
         let mut state_hash = keccak256(AlphabetClaimConstruction::abi_encode(&state_sol));
-        state_hash[0] = VMStatus::Invalid as u8;
+        if self.stamp_vm_status {
+            state_hash[0] = VMStatus::Invalid as u8;
+        }
         Ok(state_hash)
     }
 
     fn proof_at(&self, position: Position) -> anyhow::Result<Arc<[u8]>> {
         Ok(Arc::new([]))
     }
+
+    fn max_depth(&self) -> Option<u8> {
+        Some(self.max_depth)
+    }
+
+    fn override_absolute_prestate(&mut self, hash: Claim) -> anyhow::Result<()> {
+        self.absolute_prestate_hash_override = Some(hash);
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -76,10 +101,7 @@ mod test {
 
     #[test]
     fn alphabet_encoding() {
-        let provider = AlphabetTraceProvider {
-            absolute_prestate: b'a',
-            max_depth: 4,
-        };
+        let provider = AlphabetTraceProvider::new(b'a', 4);
 
         let prestate_sol = U256::from(provider.absolute_prestate()[0]);
         let prestate = <sol!(uint256)>::abi_encode(&prestate_sol);
@@ -98,10 +120,7 @@ mod test {
 
     #[test]
     fn alphabet_trace_at() {
-        let provider = AlphabetTraceProvider {
-            absolute_prestate: b'a',
-            max_depth: 4,
-        };
+        let provider = AlphabetTraceProvider::new(b'a', 4);
 
         for i in 0..16 {
             let expected = b'a' + i + 1;
@@ -116,4 +135,46 @@ mod test {
             assert_eq!(provider.state_hash(position).unwrap(), expected_hash);
         }
     }
+
+    #[test]
+    fn alphabet_raw_hashes() {
+        let mut provider = AlphabetTraceProvider::new(b'a', 4);
+        provider.stamp_vm_status = false;
+
+        let prestate_sol = U256::from(b'a');
+        assert_eq!(
+            provider.absolute_prestate_hash(),
+            keccak256(<sol!(uint256)>::abi_encode(&prestate_sol))
+        );
+
+        for i in 0..16 {
+            let position = compute_gindex(provider.max_depth, i as u64);
+            let expected_encoded = (U256::from(i), U256::from(b'a' + i + 1));
+            assert_eq!(
+                provider.state_hash(position).unwrap(),
+                keccak256(AlphabetClaimConstruction::abi_encode(&expected_encoded))
+            );
+        }
+    }
+
+    #[test]
+    fn alphabet_override_absolute_prestate() {
+        let mut provider = AlphabetTraceProvider::new(b'a', 4);
+        let honest_prestate_hash = provider.absolute_prestate_hash();
+        let honest_state_hash = provider.state_hash(Position::from(16)).unwrap();
+
+        let dishonest_prestate_hash = Claim::repeat_byte(0xFF);
+        provider
+            .override_absolute_prestate(dishonest_prestate_hash)
+            .unwrap();
+        assert_eq!(provider.absolute_prestate_hash(), dishonest_prestate_hash);
+        assert_ne!(provider.absolute_prestate_hash(), honest_prestate_hash);
+
+        // The raw absolute prestate and the rest of the trace are unaffected.
+        assert_eq!(provider.absolute_prestate()[0], b'a');
+        assert_eq!(
+            provider.state_hash(Position::from(16)).unwrap(),
+            honest_state_hash
+        );
+    }
 }
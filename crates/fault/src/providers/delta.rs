@@ -0,0 +1,199 @@
+//! This module contains the [DeltaTraceProvider], a [crate::TraceProvider] that derives each
+//! state of the trace from the state before it.
+
+use crate::{Gindex, Position, TraceProvider};
+use alloy_primitives::keccak256;
+use durin_primitives::Claim;
+use std::sync::{Arc, Mutex};
+
+type StepFn = Box<dyn Fn(&[u8]) -> Vec<u8> + Send + Sync>;
+type StateHashFn = Box<dyn Fn(u64, &[u8]) -> Claim + Send + Sync>;
+
+/// The [DeltaTraceProvider] is a [TraceProvider] for VMs whose state at a trace index is
+/// cheaply computed from the state at the previous trace index. States are derived lazily
+/// from the absolute prestate by a `step` transition and cached, so that fetching the state
+/// at a trace index reuses every state computed before it.
+///
+/// By default, the state hash at a trace index is the hash of the raw state, and the
+/// absolute prestate hash is the hash of the raw absolute prestate.
+pub struct DeltaTraceProvider {
+    /// The maximum depth of the dispute game position tree.
+    pub max_depth: u8,
+    /// The raw absolute prestate.
+    absolute_prestate: Arc<Vec<u8>>,
+    /// The absolute prestate hash served by the provider.
+    absolute_prestate_hash: Claim,
+    /// The transition from the state at one trace index to the state at the next.
+    step: StepFn,
+    /// The closure computing the state hash of the raw state at a trace index.
+    state_hash_fn: StateHashFn,
+    /// The states computed so far, indexed by trace index.
+    cache: Mutex<Vec<Arc<Vec<u8>>>>,
+}
+
+impl DeltaTraceProvider {
+    pub fn new(
+        absolute_prestate: Vec<u8>,
+        max_depth: u8,
+        step: impl Fn(&[u8]) -> Vec<u8> + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            max_depth,
+            absolute_prestate_hash: keccak256(&absolute_prestate),
+            absolute_prestate: Arc::new(absolute_prestate),
+            step: Box::new(step),
+            state_hash_fn: Box::new(|_, state| keccak256(state)),
+            cache: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Sets the absolute prestate hash served by the provider.
+    pub fn with_absolute_prestate_hash(mut self, absolute_prestate_hash: Claim) -> Self {
+        self.absolute_prestate_hash = absolute_prestate_hash;
+        self
+    }
+
+    /// Sets the closure computing the state hash of the raw state at a given trace index.
+    pub fn with_state_hash(
+        mut self,
+        state_hash_fn: impl Fn(u64, &[u8]) -> Claim + Send + Sync + 'static,
+    ) -> Self {
+        self.state_hash_fn = Box::new(state_hash_fn);
+        self
+    }
+
+    /// Returns the state at the given trace index, stepping forward from the last cached
+    /// state if it has not been computed yet.
+    fn state_at_index(&self, trace_index: u64) -> anyhow::Result<Arc<Vec<u8>>> {
+        let mut cache = self
+            .cache
+            .lock()
+            .map_err(|_| anyhow::anyhow!("Delta cache lock poisoned"))?;
+
+        while cache.len() as u64 <= trace_index {
+            let previous = cache.last().unwrap_or(&self.absolute_prestate);
+            let next = Arc::new((self.step)(previous));
+            cache.push(next);
+        }
+        Ok(cache[trace_index as usize].clone())
+    }
+}
+
+impl TraceProvider<Vec<u8>> for DeltaTraceProvider {
+    fn absolute_prestate(&self) -> Arc<Vec<u8>> {
+        self.absolute_prestate.clone()
+    }
+
+    fn absolute_prestate_hash(&self) -> Claim {
+        self.absolute_prestate_hash
+    }
+
+    fn state_at(&self, position: Position) -> anyhow::Result<Arc<Vec<u8>>> {
+        self.state_at_index(position.trace_index(self.max_depth)?)
+    }
+
+    fn state_hash(&self, position: Position) -> anyhow::Result<Claim> {
+        let trace_index = position.trace_index(self.max_depth)?;
+        let state = self.state_at_index(trace_index)?;
+        Ok((self.state_hash_fn)(trace_index, &state))
+    }
+
+    fn proof_at(&self, _: Position) -> anyhow::Result<Arc<[u8]>> {
+        Ok(Arc::new([]))
+    }
+
+    fn max_depth(&self) -> Option<u8> {
+        Some(self.max_depth)
+    }
+
+    fn override_absolute_prestate(&mut self, hash: Claim) -> anyhow::Result<()> {
+        self.absolute_prestate_hash = hash;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{AlphabetTraceProvider, VMStatus};
+    use alloy_primitives::U256;
+    use alloy_sol_types::{sol, SolType};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    type AlphabetClaimConstruction = sol! { tuple(uint256, uint256) };
+
+    /// A [DeltaTraceProvider] modeling the alphabet VM, counting the steps it takes.
+    fn alphabet_delta(steps: Arc<AtomicUsize>) -> DeltaTraceProvider {
+        let alphabet = AlphabetTraceProvider::new(b'a', 4);
+        DeltaTraceProvider::new(vec![b'a'], 4, move |state| {
+            steps.fetch_add(1, Ordering::Relaxed);
+            vec![state[0] + 1]
+        })
+        .with_absolute_prestate_hash(alphabet.absolute_prestate_hash())
+        .with_state_hash(|trace_index, state| {
+            let state_sol = (U256::from(trace_index), U256::from(state[0]));
+            let mut state_hash = keccak256(AlphabetClaimConstruction::abi_encode(&state_sol));
+            state_hash[0] = VMStatus::Invalid as u8;
+            state_hash
+        })
+    }
+
+    #[test]
+    fn delta_matches_alphabet() {
+        let provider = alphabet_delta(Arc::default());
+        let alphabet = AlphabetTraceProvider::new(b'a', 4);
+
+        assert_eq!(
+            provider.absolute_prestate_hash(),
+            alphabet.absolute_prestate_hash()
+        );
+        for position in (1..32).map(Position::from) {
+            assert_eq!(
+                provider.state_at(position).unwrap().as_slice(),
+                alphabet.state_at(position).unwrap().as_ref()
+            );
+            assert_eq!(
+                provider.state_hash(position).unwrap(),
+                alphabet.state_hash(position).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn delta_forward_fills_cache() {
+        let steps = Arc::new(AtomicUsize::new(0));
+        let provider = alphabet_delta(steps.clone());
+
+        // The root commits to the last trace index, so every state before it is computed.
+        assert_eq!(
+            provider.state_at(Position::from(1)).unwrap().as_slice(),
+            b"q"
+        );
+        assert_eq!(steps.load(Ordering::Relaxed), 16);
+        assert_eq!(provider.cache.lock().unwrap().len(), 16);
+
+        // Earlier states are served from the cache without stepping.
+        assert_eq!(
+            provider.state_at(Position::from(16)).unwrap().as_slice(),
+            b"b"
+        );
+        provider.state_hash(Position::from(2)).unwrap();
+        assert_eq!(steps.load(Ordering::Relaxed), 16);
+    }
+
+    #[test]
+    fn delta_default_hashes() {
+        let provider = DeltaTraceProvider::new(vec![0], 2, |state| vec![state[0] + 2]);
+
+        assert_eq!(provider.absolute_prestate_hash(), keccak256([0]));
+        assert_eq!(
+            provider.state_hash(Position::from(4)).unwrap(),
+            keccak256([2])
+        );
+        assert_eq!(
+            provider.state_hash(Position::from(1)).unwrap(),
+            keccak256([8])
+        );
+        assert!(provider.proof_at(Position::from(1)).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,171 @@
+//! This module contains the [FixtureTraceProvider], a [crate::TraceProvider] that serves a
+//! pre-generated trace loaded from a JSON fixture file.
+
+use crate::{Gindex, Position, TraceProvider};
+use alloy_primitives::Bytes;
+use durin_primitives::Claim;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, fs::File, io::BufReader, path::Path, sync::Arc};
+
+/// A single entry of a trace fixture, holding the state at a trace index.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceFixtureEntry {
+    /// The trace index of the entry.
+    pub trace_index: u64,
+    /// The raw state at the trace index.
+    pub state: Bytes,
+    /// The state hash at the trace index.
+    pub state_hash: Claim,
+    /// The proof for the state at the trace index.
+    #[serde(default)]
+    pub proof: Bytes,
+}
+
+/// A trace fixture, holding the absolute prestate and the full trace of a game.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TraceFixture {
+    /// The maximum depth of the dispute game position tree.
+    pub max_depth: u8,
+    /// The raw absolute prestate.
+    pub absolute_prestate: Bytes,
+    /// The absolute prestate hash.
+    pub absolute_prestate_hash: Claim,
+    /// The entries of the trace.
+    pub trace: Vec<TraceFixtureEntry>,
+}
+
+/// The [FixtureTraceProvider] is a [TraceProvider] that serves the states of a pre-generated
+/// [TraceFixture] rather than computing them.
+pub struct FixtureTraceProvider {
+    /// The maximum depth of the dispute game position tree.
+    pub max_depth: u8,
+    /// The raw absolute prestate.
+    absolute_prestate: Arc<Bytes>,
+    /// The absolute prestate hash.
+    absolute_prestate_hash: Claim,
+    /// The entries of the trace, keyed by trace index.
+    trace: HashMap<u64, TraceFixtureEntry>,
+}
+
+impl FixtureTraceProvider {
+    pub fn new(fixture: TraceFixture) -> Self {
+        Self {
+            max_depth: fixture.max_depth,
+            absolute_prestate: Arc::new(fixture.absolute_prestate),
+            absolute_prestate_hash: fixture.absolute_prestate_hash,
+            trace: fixture
+                .trace
+                .into_iter()
+                .map(|e| (e.trace_index, e))
+                .collect(),
+        }
+    }
+
+    /// Loads a [FixtureTraceProvider] from a JSON-encoded [TraceFixture] file.
+    pub fn from_file(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let file = File::open(path.as_ref()).map_err(|e| {
+            anyhow::anyhow!("Failed to open fixture {}: {}", path.as_ref().display(), e)
+        })?;
+        let fixture: TraceFixture = serde_json::from_reader(BufReader::new(file))?;
+        Ok(Self::new(fixture))
+    }
+
+    /// Fetches the fixture entry for the trace index that the given position commits to.
+    fn entry_at(&self, position: Position) -> anyhow::Result<&TraceFixtureEntry> {
+        let trace_index = position.trace_index(self.max_depth)?;
+        self.trace.get(&trace_index).ok_or(anyhow::anyhow!(
+            "Trace index {} (position {}) is not in the fixture",
+            trace_index,
+            position
+        ))
+    }
+}
+
+impl TraceProvider<Bytes> for FixtureTraceProvider {
+    fn absolute_prestate(&self) -> Arc<Bytes> {
+        self.absolute_prestate.clone()
+    }
+
+    fn absolute_prestate_hash(&self) -> Claim {
+        self.absolute_prestate_hash
+    }
+
+    fn state_at(&self, position: Position) -> anyhow::Result<Arc<Bytes>> {
+        Ok(Arc::new(self.entry_at(position)?.state.clone()))
+    }
+
+    fn state_hash(&self, position: Position) -> anyhow::Result<Claim> {
+        Ok(self.entry_at(position)?.state_hash)
+    }
+
+    fn proof_at(&self, position: Position) -> anyhow::Result<Arc<[u8]>> {
+        Ok(self.entry_at(position)?.proof.to_vec().into())
+    }
+
+    fn max_depth(&self) -> Option<u8> {
+        Some(self.max_depth)
+    }
+
+    fn override_absolute_prestate(&mut self, hash: Claim) -> anyhow::Result<()> {
+        self.absolute_prestate_hash = hash;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::AlphabetTraceProvider;
+
+    const ALPHABET_FIXTURE: &str =
+        concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/alphabet_trace.json");
+
+    #[test]
+    fn fixture_matches_alphabet() {
+        let provider = FixtureTraceProvider::from_file(ALPHABET_FIXTURE).unwrap();
+        let alphabet = AlphabetTraceProvider::new(b'a', 4);
+
+        assert_eq!(provider.max_depth, alphabet.max_depth);
+        assert_eq!(
+            provider.absolute_prestate().as_ref().as_ref(),
+            alphabet.absolute_prestate().as_ref()
+        );
+        assert_eq!(
+            provider.absolute_prestate_hash(),
+            alphabet.absolute_prestate_hash()
+        );
+        for position in (1..32).map(Position::from) {
+            assert_eq!(
+                provider.state_at(position).unwrap().as_ref().as_ref(),
+                alphabet.state_at(position).unwrap().as_ref()
+            );
+            assert_eq!(
+                provider.state_hash(position).unwrap(),
+                alphabet.state_hash(position).unwrap()
+            );
+            assert!(provider.proof_at(position).unwrap().is_empty());
+        }
+    }
+
+    #[test]
+    fn fixture_missing_position() {
+        let mut fixture: TraceFixture =
+            serde_json::from_reader(File::open(ALPHABET_FIXTURE).unwrap()).unwrap();
+        fixture.trace.retain(|e| e.trace_index != 15);
+        let provider = FixtureTraceProvider::new(fixture);
+
+        assert!(provider.state_hash(Position::from(16)).is_ok());
+        assert_eq!(
+            provider
+                .state_hash(Position::from(1))
+                .unwrap_err()
+                .to_string(),
+            "Trace index 15 (position 1) is not in the fixture"
+        );
+    }
+
+    #[test]
+    fn fixture_missing_file() {
+        assert!(FixtureTraceProvider::from_file("does/not/exist.json").is_err());
+    }
+}
@@ -0,0 +1,144 @@
+//! This module contains the [FnTraceProvider], a [crate::TraceProvider] built from closures.
+
+use crate::{Position, TraceProvider};
+use durin_primitives::Claim;
+use std::sync::Arc;
+
+type PositionFn<R> = Box<dyn Fn(Position) -> anyhow::Result<R>>;
+
+/// The [FnTraceProvider] is a [TraceProvider] that serves its trace from closures. It allows
+/// for quickly prototyping a provider inline without a full [TraceProvider] implementation.
+///
+/// Only the `state_hash` closure is required. By default, `state_at` returns an error,
+/// `proof_at` returns an empty proof, and the absolute prestate hash is [Claim::ZERO].
+pub struct FnTraceProvider<T: AsRef<[u8]>> {
+    /// The raw absolute prestate served by the provider.
+    absolute_prestate: Arc<T>,
+    /// The absolute prestate hash served by the provider.
+    absolute_prestate_hash: Claim,
+    /// The closure serving the raw state at a given position.
+    state_at_fn: PositionFn<Arc<T>>,
+    /// The closure serving the state hash at a given position.
+    state_hash_fn: PositionFn<Claim>,
+    /// The closure serving the proof at a given position.
+    proof_at_fn: PositionFn<Arc<[u8]>>,
+}
+
+impl<T: AsRef<[u8]>> FnTraceProvider<T> {
+    pub fn new(
+        absolute_prestate: T,
+        state_hash_fn: impl Fn(Position) -> anyhow::Result<Claim> + 'static,
+    ) -> Self {
+        Self {
+            absolute_prestate: Arc::new(absolute_prestate),
+            absolute_prestate_hash: Claim::ZERO,
+            state_at_fn: Box::new(|_| anyhow::bail!("state_at is not supported by this provider")),
+            state_hash_fn: Box::new(state_hash_fn),
+            proof_at_fn: Box::new(|_| Ok(Arc::new([]))),
+        }
+    }
+
+    /// Sets the absolute prestate hash served by the provider.
+    pub fn with_absolute_prestate_hash(mut self, absolute_prestate_hash: Claim) -> Self {
+        self.absolute_prestate_hash = absolute_prestate_hash;
+        self
+    }
+
+    /// Sets the closure serving the raw state at a given position.
+    pub fn with_state_at(
+        mut self,
+        state_at_fn: impl Fn(Position) -> anyhow::Result<Arc<T>> + 'static,
+    ) -> Self {
+        self.state_at_fn = Box::new(state_at_fn);
+        self
+    }
+
+    /// Sets the closure serving the proof at a given position.
+    pub fn with_proof_at(
+        mut self,
+        proof_at_fn: impl Fn(Position) -> anyhow::Result<Arc<[u8]>> + 'static,
+    ) -> Self {
+        self.proof_at_fn = Box::new(proof_at_fn);
+        self
+    }
+}
+
+impl<T: AsRef<[u8]>> TraceProvider<T> for FnTraceProvider<T> {
+    fn absolute_prestate(&self) -> Arc<T> {
+        self.absolute_prestate.clone()
+    }
+
+    fn absolute_prestate_hash(&self) -> Claim {
+        self.absolute_prestate_hash
+    }
+
+    fn state_at(&self, position: Position) -> anyhow::Result<Arc<T>> {
+        (self.state_at_fn)(position)
+    }
+
+    fn state_hash(&self, position: Position) -> anyhow::Result<Claim> {
+        (self.state_hash_fn)(position)
+    }
+
+    fn proof_at(&self, position: Position) -> anyhow::Result<Arc<[u8]>> {
+        (self.proof_at_fn)(position)
+    }
+
+    fn override_absolute_prestate(&mut self, hash: Claim) -> anyhow::Result<()> {
+        self.absolute_prestate_hash = hash;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{AlphabetTraceProvider, Gindex};
+    use alloy_primitives::U256;
+
+    #[test]
+    fn fn_provider_defaults() {
+        let provider =
+            FnTraceProvider::new([0u8; 1], |position| Ok(Claim::from(U256::from(*position))));
+
+        assert_eq!(provider.absolute_prestate().as_ref(), &[0]);
+        assert_eq!(provider.absolute_prestate_hash(), Claim::ZERO);
+        assert_eq!(
+            provider.state_hash(Position::from(5)).unwrap(),
+            Claim::with_last_byte(5)
+        );
+        assert!(provider.state_at(Position::from(5)).is_err());
+        assert!(provider.proof_at(Position::from(5)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn fn_provider_delegates() {
+        let alphabet = Arc::new(AlphabetTraceProvider::new(b'a', 4));
+        let (a, b, c) = (alphabet.clone(), alphabet.clone(), alphabet.clone());
+        let provider = FnTraceProvider::new([b'a'], move |position| a.state_hash(position))
+            .with_absolute_prestate_hash(alphabet.absolute_prestate_hash())
+            .with_state_at(move |position| b.state_at(position))
+            .with_proof_at(move |position| {
+                Ok(Arc::new([c.state_at(position)?[0], position.depth()]))
+            });
+
+        assert_eq!(
+            provider.absolute_prestate_hash(),
+            alphabet.absolute_prestate_hash()
+        );
+        for position in (1..32).map(Position::from) {
+            assert_eq!(
+                provider.state_hash(position).unwrap(),
+                alphabet.state_hash(position).unwrap()
+            );
+            assert_eq!(
+                provider.state_at(position).unwrap(),
+                alphabet.state_at(position).unwrap()
+            );
+            assert_eq!(
+                provider.proof_at(position).unwrap().as_ref(),
+                &[alphabet.state_at(position).unwrap()[0], position.depth()]
+            );
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! This module contains the [LatencyProvider], a [crate::TraceProvider] wrapper that
+//! simulates latency for testing.
+
+use crate::{AnchorInfo, Position, TraceProvider};
+use durin_primitives::Claim;
+use std::{sync::Arc, thread, time::Duration};
+
+/// The [LatencyProvider] wraps a [TraceProvider] and sleeps for a configurable duration
+/// before each delegated call, modeling a slow backend.
+pub struct LatencyProvider<P> {
+    /// The wrapped [TraceProvider].
+    pub inner: P,
+    /// The delay applied before fetching the raw state at a position.
+    pub state_at_delay: Duration,
+    /// The delay applied before fetching the state hash at a position.
+    pub state_hash_delay: Duration,
+    /// The delay applied before fetching the proof at a position.
+    pub proof_at_delay: Duration,
+}
+
+impl<P> LatencyProvider<P> {
+    /// Creates a new [LatencyProvider] that applies the same delay to every fetch.
+    pub fn new(inner: P, delay: Duration) -> Self {
+        Self {
+            inner,
+            state_at_delay: delay,
+            state_hash_delay: delay,
+            proof_at_delay: delay,
+        }
+    }
+}
+
+impl<T, P> TraceProvider<T> for LatencyProvider<P>
+where
+    T: AsRef<[u8]>,
+    P: TraceProvider<T>,
+{
+    fn absolute_prestate(&self) -> Arc<T> {
+        self.inner.absolute_prestate()
+    }
+
+    fn absolute_prestate_hash(&self) -> Claim {
+        self.inner.absolute_prestate_hash()
+    }
+
+    fn state_at(&self, position: Position) -> anyhow::Result<Arc<T>> {
+        thread::sleep(self.state_at_delay);
+        self.inner.state_at(position)
+    }
+
+    fn state_hash(&self, position: Position) -> anyhow::Result<Claim> {
+        thread::sleep(self.state_hash_delay);
+        self.inner.state_hash(position)
+    }
+
+    fn proof_at(&self, position: Position) -> anyhow::Result<Arc<[u8]>> {
+        thread::sleep(self.proof_at_delay);
+        self.inner.proof_at(position)
+    }
+
+    fn max_depth(&self) -> Option<u8> {
+        self.inner.max_depth()
+    }
+
+    fn anchor(&self) -> AnchorInfo {
+        self.inner.anchor()
+    }
+
+    fn override_absolute_prestate(&mut self, hash: Claim) -> anyhow::Result<()> {
+        self.inner.override_absolute_prestate(hash)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::AlphabetTraceProvider;
+    use std::time::Instant;
+
+    #[test]
+    fn latency_per_method() {
+        let mut provider =
+            LatencyProvider::new(AlphabetTraceProvider::new(b'a', 4), Duration::ZERO);
+        provider.proof_at_delay = Duration::from_millis(50);
+
+        assert_eq!(
+            provider.state_hash(Position::from(16)).unwrap(),
+            provider.inner.state_hash(Position::from(16)).unwrap()
+        );
+
+        let start = Instant::now();
+        assert!(provider.proof_at(Position::from(16)).unwrap().is_empty());
+        assert!(start.elapsed() >= provider.proof_at_delay);
+    }
+}
@@ -2,3 +2,96 @@
 
 mod alphabet;
 pub use self::alphabet::AlphabetTraceProvider;
+
+mod delta;
+pub use self::delta::DeltaTraceProvider;
+
+mod fixture;
+pub use self::fixture::{FixtureTraceProvider, TraceFixture, TraceFixtureEntry};
+
+mod function;
+pub use self::function::FnTraceProvider;
+
+mod validating;
+pub use self::validating::ValidatingProvider;
+
+#[cfg(test)]
+mod latency;
+#[cfg(test)]
+pub use self::latency::LatencyProvider;
+
+use crate::{Position, TraceProvider};
+use durin_primitives::Claim;
+use std::sync::Arc;
+
+/// Converts a raw state returned by a [crate::TraceProvider] into a [Claim], validating
+/// that the state is exactly 32 bytes in length.
+///
+/// ### Takes
+/// - `state`: The raw state to convert.
+///
+/// ### Returns
+/// - [Claim] or [Err]: The state as a [Claim], or an error if its length is not 32 bytes.
+pub fn as_b256(state: &Arc<[u8]>) -> anyhow::Result<Claim> {
+    if state.len() != 32 {
+        anyhow::bail!("expected 32-byte state, got {}", state.len());
+    }
+    Ok(Claim::from_slice(state))
+}
+
+/// Computes the root claim that commits to the entirety of a provider's trace, for a
+/// proposer to post when creating a game. This is the provider's state hash at the root
+/// position.
+///
+/// ### Takes
+/// - `provider`: The [TraceProvider] serving the complete trace.
+/// - `max_depth`: The max depth of the game's position tree.
+///
+/// ### Returns
+/// - [Claim] or [Err]: The root claim, or an error if the provider advertises a different max
+///   depth or fails.
+pub fn root_claim_from_trace<T, P>(provider: &P, max_depth: u8) -> anyhow::Result<Claim>
+where
+    T: AsRef<[u8]>,
+    P: TraceProvider<T>,
+{
+    match provider.max_depth() {
+        Some(provider_depth) if provider_depth != max_depth => anyhow::bail!(
+            "Provider max depth {} does not match the game max depth {}",
+            provider_depth,
+            max_depth
+        ),
+        _ => provider.state_hash(Position::ROOT),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn as_b256_valid() {
+        let state: Arc<[u8]> = Arc::new([0xFF; 32]);
+        assert_eq!(as_b256(&state).unwrap(), Claim::repeat_byte(0xFF));
+    }
+
+    #[test]
+    fn as_b256_invalid_length() {
+        let state: Arc<[u8]> = Arc::new([0xFF; 31]);
+        let err = as_b256(&state).unwrap_err();
+        assert_eq!(err.to_string(), "expected 32-byte state, got 31");
+    }
+
+    #[test]
+    fn root_claim_from_alphabet_trace() {
+        let provider = AlphabetTraceProvider::new(b'a', 4);
+        assert_eq!(
+            root_claim_from_trace(&provider, 4).unwrap(),
+            provider.state_hash(Position::from(1)).unwrap()
+        );
+        assert_eq!(
+            root_claim_from_trace(&provider, 5).unwrap_err().to_string(),
+            "Provider max depth 4 does not match the game max depth 5"
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! This module contains the [ValidatingProvider], a [crate::TraceProvider] wrapper that
+//! checks the length of every raw state served by the wrapped provider.
+
+use crate::{AnchorInfo, Position, TraceProvider};
+use durin_primitives::Claim;
+use std::sync::Arc;
+
+/// The [ValidatingProvider] wraps a [TraceProvider] and checks that every raw state it serves
+/// is of the expected length, failing at the source with the offending position rather than
+/// deep in the call path of a consumer. It is a debugging aid for integrating new backends.
+pub struct ValidatingProvider<P> {
+    /// The wrapped [TraceProvider].
+    pub inner: P,
+    /// The expected length of each raw state, in bytes.
+    pub state_len: usize,
+}
+
+impl<P> ValidatingProvider<P> {
+    /// Creates a new [ValidatingProvider], checking the length of the wrapped provider's
+    /// absolute prestate.
+    ///
+    /// ### Takes
+    /// - `inner`: The [TraceProvider] to wrap.
+    /// - `state_len`: The expected length of each raw state, in bytes.
+    ///
+    /// ### Returns
+    /// - [ValidatingProvider] or [Err]: The wrapped provider, or an error if its absolute
+    ///   prestate is not of the expected length.
+    pub fn new<T>(inner: P, state_len: usize) -> anyhow::Result<Self>
+    where
+        T: AsRef<[u8]>,
+        P: TraceProvider<T>,
+    {
+        let prestate_len = inner.absolute_prestate().as_ref().as_ref().len();
+        if prestate_len != state_len {
+            anyhow::bail!(
+                "Absolute prestate has length {}, expected {}",
+                prestate_len,
+                state_len
+            );
+        }
+        Ok(Self { inner, state_len })
+    }
+}
+
+impl<T, P> TraceProvider<T> for ValidatingProvider<P>
+where
+    T: AsRef<[u8]>,
+    P: TraceProvider<T>,
+{
+    fn absolute_prestate(&self) -> Arc<T> {
+        self.inner.absolute_prestate()
+    }
+
+    fn absolute_prestate_hash(&self) -> Claim {
+        self.inner.absolute_prestate_hash()
+    }
+
+    fn state_at(&self, position: Position) -> anyhow::Result<Arc<T>> {
+        let state = self.inner.state_at(position)?;
+        let len = state.as_ref().as_ref().len();
+        if len != self.state_len {
+            anyhow::bail!(
+                "State at position {} has length {}, expected {}",
+                position,
+                len,
+                self.state_len
+            );
+        }
+        Ok(state)
+    }
+
+    fn state_hash(&self, position: Position) -> anyhow::Result<Claim> {
+        self.inner.state_hash(position)
+    }
+
+    fn proof_at(&self, position: Position) -> anyhow::Result<Arc<[u8]>> {
+        self.inner.proof_at(position)
+    }
+
+    fn max_depth(&self) -> Option<u8> {
+        self.inner.max_depth()
+    }
+
+    fn anchor(&self) -> AnchorInfo {
+        self.inner.anchor()
+    }
+
+    fn override_absolute_prestate(&mut self, hash: Claim) -> anyhow::Result<()> {
+        self.inner.override_absolute_prestate(hash)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{AlphabetTraceProvider, FnTraceProvider};
+
+    #[test]
+    fn validating_rejects_wrong_length() {
+        let provider =
+            FnTraceProvider::new(vec![0u8; 32], |_| Ok(Claim::ZERO)).with_state_at(|position| {
+                let len = if position == Position::from(5) {
+                    31
+                } else {
+                    32
+                };
+                Ok(Arc::new(vec![0u8; len]))
+            });
+        let provider = ValidatingProvider::new(provider, 32).unwrap();
+
+        assert_eq!(provider.state_at(Position::from(4)).unwrap().len(), 32);
+        assert_eq!(
+            provider
+                .state_at(Position::from(5))
+                .unwrap_err()
+                .to_string(),
+            "State at position 5 has length 31, expected 32"
+        );
+    }
+
+    #[test]
+    fn validating_rejects_wrong_prestate_length() {
+        let provider = FnTraceProvider::new(vec![0u8; 31], |_| Ok(Claim::ZERO));
+        assert_eq!(
+            ValidatingProvider::new(provider, 32)
+                .err()
+                .unwrap()
+                .to_string(),
+            "Absolute prestate has length 31, expected 32"
+        );
+    }
+
+    #[test]
+    fn validating_delegates() {
+        let provider = ValidatingProvider::new(AlphabetTraceProvider::new(b'a', 4), 1).unwrap();
+        let alphabet = AlphabetTraceProvider::new(b'a', 4);
+
+        assert_eq!(provider.max_depth(), Some(4));
+        assert_eq!(
+            provider.anchor(),
+            AnchorInfo {
+                block_number: None,
+                prestate_hash: alphabet.absolute_prestate_hash(),
+            }
+        );
+        for position in (1..32).map(Position::from) {
+            assert_eq!(
+                provider.state_at(position).unwrap(),
+                alphabet.state_at(position).unwrap()
+            );
+            assert_eq!(
+                provider.state_hash(position).unwrap(),
+                alphabet.state_hash(position).unwrap()
+            );
+        }
+    }
+}
@@ -0,0 +1,44 @@
+//! This module contains built-in [ResolutionRule]s for use with
+//! [FaultDisputeState::resolve_with_rules].
+//!
+//! Resolution rules check invariants of a [FaultDisputeState] before or after its state DAG is
+//! resolved, and fail resolution if any invariant is violated.
+
+use crate::{FaultDisputeGame, FaultDisputeState};
+use durin_primitives::{DisputeGame, GameStatus};
+
+/// A [durin_primitives::rule::Rule] over a borrowed [FaultDisputeState]. The rule accepts a
+/// borrow of any lifetime, so that it can be checked against the state in place, both before
+/// and after the state is resolved, without copying it.
+pub type ResolutionRule = Box<dyn Fn(&FaultDisputeState) -> anyhow::Result<&FaultDisputeState>>;
+
+/// Returns a [ResolutionRule] checking that the status of the game is terminal, i.e. that the
+/// game is no longer in progress. Intended to be checked after resolution.
+pub fn root_status_terminal() -> ResolutionRule {
+    Box::new(|state: &FaultDisputeState| {
+        if *state.status() == GameStatus::InProgress {
+            anyhow::bail!("Root status is not terminal");
+        }
+        Ok(state)
+    })
+}
+
+/// Returns a [ResolutionRule] checking that no claim is both resolved and unresolved, i.e. that
+/// every claim within a resolved subgame has had its own subgame resolved as well.
+pub fn resolved_subgames_consistent() -> ResolutionRule {
+    Box::new(|state: &FaultDisputeState| {
+        let inconsistent = state.state().iter().enumerate().find(|(i, claim)| {
+            claim.parent_index != u32::MAX
+                && state.is_subgame_resolved(claim.parent_index as usize)
+                && !state.is_subgame_resolved(*i)
+        });
+        if let Some((i, claim)) = inconsistent {
+            anyhow::bail!(
+                "Claim {} is resolved, but the subgame of its child {} is not",
+                claim.parent_index,
+                i
+            );
+        }
+        Ok(state)
+    })
+}
@@ -0,0 +1,232 @@
+//! This module contains the [GameRunner], an in-memory [crate::FaultDisputeGame] that plays the
+//! honest solver against a random adversary from a seed, for deterministic fuzzing of the whole
+//! solving and resolution pipeline.
+
+use crate::{
+    AlphaClaimSolver, AlphabetTraceProvider, ChessClock, ClaimData, Clock, FaultDisputeGame,
+    FaultDisputeSolver, FaultDisputeState, FaultSolverResponse, Gindex, Position,
+};
+use alloy_primitives::Address;
+use durin_primitives::{Claim, DisputeSolver, GameStatus};
+
+/// The max depth of the position trees of the games played by the [GameRunner].
+const MAX_DEPTH: u8 = 4;
+
+/// The claimant of the honest side's claims.
+const HONEST: Address = Address::repeat_byte(0x11);
+
+/// The claimant of the adversary's claims.
+const ADVERSARY: Address = Address::repeat_byte(0x22);
+
+/// A small, seedable pseudo-random number generator (SplitMix64), so that every run of the
+/// [GameRunner] is reproducible from its seed.
+struct SplitMix64(u64);
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    fn below(&mut self, n: u64) -> u64 {
+        self.next() % n
+    }
+}
+
+/// The [GameRunner] plays a game over the alphabet trace between the honest solver and an
+/// adversary making random legal moves, advancing a mock clock between rounds.
+///
+/// The adversary only counters claims on the honest side of the game, posting either the
+/// honest value, the honest value with a different VM status byte, or garbage. The honest
+/// side responds to every new claim after each round. Steps made by the honest side are
+/// assumed to succeed, as the alphabet trace has no VM to execute them against.
+struct GameRunner {
+    rng: SplitMix64,
+    solver: FaultDisputeSolver<
+        [u8; 1],
+        AlphabetTraceProvider,
+        AlphaClaimSolver<[u8; 1], AlphabetTraceProvider>,
+    >,
+    state: FaultDisputeState,
+    now: u64,
+}
+
+impl GameRunner {
+    /// Creates a new [GameRunner] from a seed. The seed decides whether the root claim is
+    /// honest, in which case the honest side defends it and the adversary challenges it.
+    fn new(seed: u64) -> Self {
+        let mut rng = SplitMix64(seed);
+        let solver = FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(
+            b'a', MAX_DEPTH,
+        )));
+
+        let honest_root = rng.below(2) == 0;
+        let (root_claim, claimant) = if honest_root {
+            (solver.honest_claim_at(Position::ROOT).unwrap(), HONEST)
+        } else {
+            (Claim::repeat_byte(0xFF), ADVERSARY)
+        };
+        let state = FaultDisputeState::new(
+            vec![ClaimData {
+                parent_index: u32::MAX,
+                visited: false,
+                value: root_claim,
+                position: Position::ROOT,
+                clock: 0,
+                claimant,
+                countered_by: Address::ZERO,
+            }],
+            root_claim,
+            GameStatus::InProgress,
+            MAX_DEPTH,
+        );
+
+        Self {
+            rng,
+            solver,
+            state,
+            now: 0,
+        }
+    }
+
+    /// Whether or not the claim at the given index is on the honest side of the game, i.e.
+    /// whether the honest side supports it.
+    fn is_honest_side(&self, index: usize) -> bool {
+        let attacking_root = self.state.state()[0].claimant != HONEST;
+        self.state.state()[index].position.depth() % 2 == attacking_root as u8
+    }
+
+    /// Appends a move against the claim at `parent_index`, with the clock that the contract
+    /// would assign to it at the current time.
+    fn push_move(&mut self, parent_index: usize, is_attack: bool, value: Claim, claimant: Address) {
+        let state = self.state.state();
+        let parent = &state[parent_index];
+        let grandparent_duration = state
+            .get(parent.parent_index as usize)
+            .map(|c| c.clock.duration())
+            .unwrap_or_default();
+        let duration = grandparent_duration + (self.now - parent.clock.timestamp());
+        let clock: Clock = ((duration as u128) << 64) | self.now as u128;
+
+        let position = parent.position.make_move(is_attack);
+        self.state.state_mut().push(ClaimData {
+            parent_index: parent_index as u32,
+            visited: claimant == HONEST,
+            value,
+            position,
+            clock,
+            claimant,
+            countered_by: Address::ZERO,
+        });
+    }
+
+    /// Makes a random number of random legal moves on behalf of the adversary.
+    fn adversary_round(&mut self) {
+        for _ in 0..self.rng.below(4) {
+            let targets = (0..self.state.state().len())
+                .filter(|i| self.is_honest_side(*i))
+                .collect::<Vec<_>>();
+            let parent_index = targets[self.rng.below(targets.len() as u64) as usize];
+            let is_attack = self.rng.below(2) == 0;
+            if self
+                .state
+                .is_move_legal(parent_index, is_attack, self.now)
+                .is_err()
+            {
+                continue;
+            }
+
+            let position = self.state.state()[parent_index]
+                .position
+                .make_move(is_attack);
+            let honest = self.solver.honest_claim_at(position).unwrap();
+            let value = match self.rng.below(3) {
+                0 => honest,
+                1 => {
+                    let mut claim = honest;
+                    claim[0] ^= 1;
+                    claim
+                }
+                _ => Claim::repeat_byte(self.rng.below(256) as u8),
+            };
+            self.push_move(parent_index, is_attack, value, ADVERSARY);
+        }
+    }
+
+    /// Responds to every new claim on behalf of the honest side. Every response must be
+    /// accepted by the contract.
+    fn honest_round(&mut self) {
+        let responses = self.solver.available_moves(&mut self.state).unwrap();
+        for response in responses.iter() {
+            match response {
+                FaultSolverResponse::Move(is_attack, parent_index, claim) => {
+                    self.state
+                        .is_move_legal(*parent_index, *is_attack, self.now)
+                        .unwrap();
+                    self.push_move(*parent_index, *is_attack, *claim, HONEST);
+                }
+                FaultSolverResponse::Step(_, claim_index, _, _) => {
+                    self.state.state_mut()[*claim_index].countered_by = HONEST;
+                }
+                FaultSolverResponse::Skip(_) | FaultSolverResponse::Resolve(_) => {}
+                FaultSolverResponse::Defer(_) => panic!("The alphabet provider never defers"),
+            }
+        }
+    }
+
+    /// Plays the game for a number of rounds, then lets every clock expire and resolves each
+    /// subgame bottom-up as the contract would.
+    ///
+    /// ### Returns
+    /// - Whether or not the honest side won the game.
+    fn run(mut self, rounds: usize) -> bool {
+        self.honest_round();
+        for _ in 0..rounds {
+            self.now += self.rng.below(3600);
+            self.adversary_round();
+            self.now += self.rng.below(3600);
+            self.honest_round();
+        }
+
+        self.now += self.state.game_duration;
+        let outcomes = self.state.subgame_outcomes(self.now).unwrap();
+        for index in self.state.topological_order().unwrap().into_iter().rev() {
+            self.state.resolve_subgame(index, self.now).unwrap();
+        }
+
+        // The bottom-up resolution must agree with the single-pass outcomes.
+        let root_countered = self.state.state()[0].countered_by != Address::ZERO;
+        assert_eq!(outcomes[0], (0, root_countered));
+
+        let honest_root = self.state.state()[0].claimant == HONEST;
+        root_countered != honest_root
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn honest_side_always_wins() {
+        for seed in 0..64 {
+            assert!(
+                GameRunner::new(seed).run(32),
+                "Honest side lost seed {}",
+                seed
+            );
+        }
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(32))]
+        #[test]
+        fn honest_side_always_wins_fuzz(seed in any::<u64>(), rounds in 0..64usize) {
+            prop_assert!(GameRunner::new(seed).run(rounds));
+        }
+    }
+}
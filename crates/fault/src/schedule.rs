@@ -0,0 +1,118 @@
+//! This module contains the [MoveScheduler], which orders the responses computed by a solver
+//! by the deadlines of the clocks of the claims they counter.
+
+use crate::{FaultDisputeState, FaultSolverResponse};
+
+/// The [MoveScheduler] schedules the responses computed by a solver against the clocks of the
+/// claims they counter, so that an agent can hold back its moves until they are needed and
+/// submit the most time-critical ones first.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MoveScheduler {
+    /// The number of seconds before a clock expires by which a response against its claim
+    /// must be submitted, leaving time for the transaction to be included.
+    pub margin: u64,
+}
+
+impl MoveScheduler {
+    pub fn new(margin: u64) -> Self {
+        Self { margin }
+    }
+
+    /// Schedules the moves and steps among the responses by the deadline of the claim each
+    /// counters. Resolutions have no deadline, and are scheduled last. Skips and deferrals
+    /// require no action, and responses against claims whose clocks have expired can no
+    /// longer be submitted, so neither are scheduled.
+    ///
+    /// ### Takes
+    /// - `world`: The [FaultDisputeState] that the responses were computed against.
+    /// - `responses`: The responses computed by the solver.
+    /// - `now`: The current timestamp, in seconds.
+    ///
+    /// ### Returns
+    /// - Each response paired with the timestamp by which it must be submitted, ordered by
+    ///   urgency. Responses with the same deadline are ordered by claim index.
+    pub fn schedule<T: AsRef<[u8]>>(
+        &self,
+        world: &FaultDisputeState,
+        responses: impl IntoIterator<Item = FaultSolverResponse<T>>,
+        now: u64,
+    ) -> Vec<(FaultSolverResponse<T>, u64)> {
+        let mut schedule = responses
+            .into_iter()
+            .filter_map(|response| {
+                let submit_by = match response {
+                    FaultSolverResponse::Move(..) | FaultSolverResponse::Step(..) => {
+                        let expiry = world.clock_expiry(response.claim_index())?;
+                        (expiry >= now).then(|| expiry.saturating_sub(self.margin).max(now))?
+                    }
+                    FaultSolverResponse::Resolve(_) => u64::MAX,
+                    FaultSolverResponse::Skip(_) | FaultSolverResponse::Defer(_) => return None,
+                };
+                Some((response, submit_by))
+            })
+            .collect::<Vec<_>>();
+        schedule.sort_by_key(|(response, submit_by)| (*submit_by, response.claim_index()));
+        schedule
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{state::alphabet_claim, ClaimData, Position};
+    use durin_primitives::{Claim, GameStatus};
+
+    #[test]
+    fn urgent_moves_first() {
+        let claim = |parent_index, position, clock| ClaimData {
+            clock,
+            ..alphabet_claim(parent_index, position, Claim::ZERO)
+        };
+        // Claim 3 counters claim 2, whose side of the clock is nearly exhausted, so it expires
+        // well before claims 1 and 2.
+        let half = crate::DEFAULT_GAME_DURATION >> 1;
+        let state = FaultDisputeState::new(
+            vec![
+                claim(u32::MAX, Position::from(1), 0),
+                claim(0, Position::from(2), 100),
+                claim(1, Position::from(4), ((half as u128 - 1_000) << 64) | 2_000),
+                claim(2, Position::from(8), (5 << 64) | 3_000),
+            ],
+            Claim::ZERO,
+            GameStatus::InProgress,
+            4,
+        );
+        assert_eq!(state.clock_expiry(1), Some(100 + half));
+        assert_eq!(state.clock_expiry(2), Some(2_000 + half));
+        assert_eq!(state.clock_expiry(3), Some(4_000));
+
+        let scheduler = MoveScheduler::new(60);
+        let responses = vec![
+            FaultSolverResponse::<[u8; 1]>::Resolve(0),
+            FaultSolverResponse::Move(true, 1, Claim::ZERO),
+            FaultSolverResponse::Skip(2),
+            FaultSolverResponse::Move(false, 2, Claim::ZERO),
+            FaultSolverResponse::Move(true, 3, Claim::ZERO),
+        ];
+        assert_eq!(
+            scheduler.schedule(&state, responses.clone(), 3_500),
+            vec![
+                (responses[4].clone(), 3_940),
+                (responses[1].clone(), 40 + half),
+                (responses[3].clone(), 1_940 + half),
+                (responses[0].clone(), u64::MAX),
+            ]
+        );
+
+        // A response within the margin of its deadline must be submitted immediately, and a
+        // response against an expired claim is dropped.
+        assert_eq!(
+            scheduler.schedule(&state, responses.clone(), 3_970)[0],
+            (responses[4].clone(), 3_970)
+        );
+        assert_eq!(
+            scheduler.schedule(&state, responses.clone(), 4_001)[0],
+            (responses[1].clone(), 40 + half)
+        );
+    }
+}
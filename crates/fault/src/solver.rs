@@ -1,16 +1,26 @@
 //! This module contains the various implementations of the [crate::FaultDisputeSolver] trait.
 
 use crate::{
-    FaultClaimSolver, FaultDisputeGame, FaultDisputeState, FaultSolverResponse, Position,
+    checked_gindex, claims_equal_ignoring_status, compute_gindex, BisectionConvention, ClaimDag,
+    DeadlineMoves, DecisionRecord, FaultClaimSolver, FaultDisputeGame, FaultDisputeState,
+    FaultSolverResponse, Gindex, MoveReason, MoveReport, Position, ProviderError, StateDiff,
     TraceProvider,
 };
-use durin_primitives::{DisputeGame, DisputeSolver};
-use std::{marker::PhantomData, sync::Arc};
+use alloy_primitives::Address;
+use durin_primitives::{Claim, DisputeGame, DisputeSolver};
+use std::{marker::PhantomData, sync::Arc, time::Instant};
 
 /// A [FaultDisputeSolver] is a [DisputeSolver] that is played over a fault proof VM backend. The
 /// solver is responsible for honestly responding to any given [ClaimData] in a given
 /// [FaultDisputeState]. It uses a [TraceProvider] to fetch the absolute prestate of the VM as
 /// well as the state at any given [Position] within the tree.
+///
+/// The responses returned by the solver are always ordered by the index of the claim that
+/// produced them, independent of how long the provider takes to serve each claim.
+///
+/// Each claim solved in [DisputeSolver::available_moves] is wrapped in a debug-level
+/// `solve_claim` [tracing] span, and provider calls are wrapped in `provider.*` spans. Timing
+/// for each span can be collected by installing a subscriber that records span durations.
 pub struct FaultDisputeSolver<T, P, S>
 where
     T: AsRef<[u8]>,
@@ -18,6 +28,16 @@ where
     S: FaultClaimSolver<T, P>,
 {
     pub inner: S,
+    /// When enabled, a claim whose local opinion cannot be fetched from the provider yields a
+    /// [FaultSolverResponse::Defer] rather than failing the entire pass in
+    /// [DisputeSolver::available_moves]. Any other error still fails the pass.
+    pub defer_unavailable: bool,
+    /// The [BisectionConvention] of the game's contract. Claim solvers reason in the canonical
+    /// convention, and the directions of their moves and steps are mapped onto this convention.
+    pub convention: BisectionConvention,
+    /// The address that the solver submits its moves from, if known. The solver never
+    /// counters a claim made by this address, even if it would otherwise disagree with it.
+    pub address: Option<Address>,
     _phantom_t: PhantomData<T>,
     _phantom_p: PhantomData<P>,
 }
@@ -31,6 +51,34 @@ where
     pub fn provider(&self) -> &P {
         self.inner.provider()
     }
+
+    /// Returns the local opinion of the claim at the given position, independent of any game
+    /// state. This is the state hash served by the provider, unless the claim solver overrides
+    /// it.
+    ///
+    /// ### Takes
+    /// - `position`: The [Position] of the claim.
+    ///
+    /// ### Returns
+    /// - [Claim] or [Err]: The honest claim at the position, or an error if the provider
+    ///   fails.
+    pub fn honest_claim_at(&self, position: Position) -> anyhow::Result<Claim> {
+        self.inner.state_hash(position)
+    }
+
+    /// Returns the local opinion of the claim at the position with the given depth and index
+    /// at depth. See [FaultDisputeSolver::honest_claim_at].
+    ///
+    /// ### Takes
+    /// - `depth`: The depth of the position.
+    /// - `index_at_depth`: The index at depth of the position.
+    ///
+    /// ### Returns
+    /// - [Claim] or [Err]: The honest claim at the position, or an error if the index is out
+    ///   of bounds at the depth or the provider fails.
+    pub fn state_hash_at(&self, depth: u8, index_at_depth: u64) -> anyhow::Result<Claim> {
+        self.honest_claim_at(checked_gindex(depth, index_at_depth)?)
+    }
 }
 
 impl<T, P, S> DisputeSolver<FaultDisputeState, FaultSolverResponse<T>>
@@ -44,23 +92,8 @@ where
         &self,
         game: &mut FaultDisputeState,
     ) -> anyhow::Result<Arc<[FaultSolverResponse<T>]>> {
-        // Fetch the local opinion on the root claim.
-        let attacking_root =
-            self.provider().state_hash(Self::ROOT_CLAIM_POSITION)? != game.root_claim();
-
-        // Fetch the indices of all unvisited claims within the world DAG.
-        let unvisited_indices = game
-            .state()
-            .iter()
-            .enumerate()
-            .filter_map(|(i, c)| (!c.visited).then_some(i))
-            .collect::<Vec<_>>();
-
-        // Solve each unvisited claim, set the visited flag, and return the responses.
-        unvisited_indices
-            .iter()
-            .map(|claim_index| self.inner.solve_claim(game, *claim_index, attacking_root))
-            .collect()
+        let unvisited_indices = Self::unvisited_indices(game);
+        self.solve_claims(game, &unvisited_indices).map(Into::into)
     }
 }
 
@@ -70,13 +103,2370 @@ where
     P: TraceProvider<T>,
     S: FaultClaimSolver<T, P>,
 {
-    const ROOT_CLAIM_POSITION: Position = 1;
+    const ROOT_CLAIM_POSITION: Position = Position::ROOT;
 
     pub fn new(claim_solver: S) -> Self {
         Self {
             inner: claim_solver,
+            defer_unavailable: false,
+            convention: BisectionConvention::default(),
+            address: None,
             _phantom_t: PhantomData,
             _phantom_p: PhantomData,
         }
     }
+
+    /// Returns the available responses for the claims that changed in the passed [StateDiff].
+    /// Unvisited claims that were added are solved, along with every claim that was newly
+    /// countered, which was already visited by an earlier pass. This produces the same responses
+    /// as a full pass of [DisputeSolver::available_moves] restricted to the changed claims.
+    ///
+    /// ### Takes
+    /// - `world`: The [FaultDisputeState] to solve against.
+    /// - `since`: The [StateDiff] describing the claims that changed since the last pass.
+    ///
+    /// ### Returns
+    /// - The responses for the changed claims, ordered by claim index.
+    pub fn available_moves_incremental(
+        &self,
+        world: &mut FaultDisputeState,
+        since: &StateDiff,
+    ) -> anyhow::Result<Arc<[FaultSolverResponse<T>]>> {
+        let added = since
+            .added
+            .iter()
+            .filter(|i| world.state().get(**i).is_some_and(|c| !c.visited));
+        let countered = since.countered.iter().filter(|i| **i < world.state().len());
+        let mut changed_indices = added.chain(countered).copied().collect::<Vec<_>>();
+        changed_indices.sort_unstable();
+        changed_indices.dedup();
+
+        self.solve_claims(world, &changed_indices).map(Into::into)
+    }
+
+    /// Returns the same responses as [DisputeSolver::available_moves], along with a
+    /// [FaultSolverResponse::Resolve] for each subgame that can be resolved in favor of the
+    /// local opinion at the given timestamp. A subgame can be resolved once the clock of its
+    /// root claim has expired and the subgames of all claims that effectively counter it have
+    /// been resolved. A subgame is resolved in favor of the local opinion if its root claim
+    /// ends up countered when it opposes the local opinion, or uncountered when it supports it.
+    ///
+    /// ### Takes
+    /// - `world`: The [FaultDisputeState] to solve against.
+    /// - `now`: The current timestamp, in seconds.
+    ///
+    /// ### Returns
+    /// - The responses for the unvisited claims and the resolvable subgames, ordered by claim
+    ///   index.
+    pub fn available_moves_at(
+        &self,
+        world: &mut FaultDisputeState,
+        now: u64,
+    ) -> anyhow::Result<Arc<[FaultSolverResponse<T>]>> {
+        let attacking_root = self.attacking_root(world)?;
+        let unvisited_indices = Self::unvisited_indices(world);
+        let mut responses =
+            self.solve_claims_until(world, &unvisited_indices, attacking_root, None)?;
+
+        for (index, countered) in world.resolvable_subgames(now) {
+            let supports = world.state()[index].position.depth() % 2 == attacking_root as u8;
+            if countered != supports {
+                responses.push(FaultSolverResponse::Resolve(index));
+            }
+        }
+
+        responses.sort_by_key(|r| r.claim_index());
+        Ok(responses.into())
+    }
+
+    /// Returns the same responses as [DisputeSolver::available_moves] without mutating the
+    /// passed state. The visited flags are tracked locally for the duration of the pass, so
+    /// callers may solve against a shared snapshot while holding only read access to it.
+    ///
+    /// ### Takes
+    /// - `world`: The [FaultDisputeState] to solve against.
+    ///
+    /// ### Returns
+    /// - The responses for the unvisited claims, ordered by claim index.
+    pub fn available_moves_readonly(
+        &self,
+        world: &FaultDisputeState,
+    ) -> anyhow::Result<Arc<[FaultSolverResponse<T>]>> {
+        let attacking_root = self.attacking_root(world)?;
+        let mut visited = world.state().iter().map(|c| c.visited).collect::<Vec<_>>();
+        let unvisited_indices = Self::unvisited_indices(world);
+        self.solve_claims_tracked(
+            world,
+            &unvisited_indices,
+            &mut visited,
+            attacking_root,
+            None,
+        )
+        .map(Into::into)
+    }
+
+    /// Returns the same responses as [DisputeSolver::available_moves], but stops solving
+    /// claims once the deadline has passed, so that the caller can act on the responses that
+    /// were computed before its window to submit them closes. The deadline is checked before
+    /// each claim is solved, so a single provider call that outlives the deadline is not
+    /// interrupted.
+    ///
+    /// ### Takes
+    /// - `world`: The [FaultDisputeState] to solve against.
+    /// - `deadline`: The [Instant] after which no more claims are solved.
+    ///
+    /// ### Returns
+    /// - The [DeadlineMoves] holding the responses computed before the deadline and the
+    ///   claims that were not solved in time.
+    pub fn available_moves_with_deadline(
+        &self,
+        world: &mut FaultDisputeState,
+        deadline: Instant,
+    ) -> anyhow::Result<DeadlineMoves<T>> {
+        let attacking_root = self.attacking_root(world)?;
+        let unvisited_indices = Self::unvisited_indices(world);
+        let responses =
+            self.solve_claims_until(world, &unvisited_indices, attacking_root, Some(deadline))?;
+        let timed_out = unvisited_indices[responses.len()..].to_vec();
+        Ok(DeadlineMoves {
+            responses,
+            timed_out,
+        })
+    }
+
+    /// Returns the same responses as [DisputeSolver::available_moves], each annotated with the
+    /// reasoning behind it. The claim solver is not aware of the reports, so the local opinion
+    /// of each claim is fetched from the provider a second time.
+    ///
+    /// ### Takes
+    /// - `world`: The [FaultDisputeState] to solve against.
+    ///
+    /// ### Returns
+    /// - A [MoveReport] for each unvisited claim, ordered by claim index.
+    pub fn available_moves_verbose(
+        &self,
+        world: &mut FaultDisputeState,
+    ) -> anyhow::Result<Vec<MoveReport<T>>> {
+        let unvisited_indices = Self::unvisited_indices(world);
+        let responses = self.solve_claims(world, &unvisited_indices)?;
+
+        Ok(responses
+            .into_iter()
+            .map(|response| {
+                let claim_index = response.claim_index();
+                let claim = &world.state()[claim_index];
+                let reason = match &response {
+                    FaultSolverResponse::Skip(_)
+                        if claim.parent_index != u32::MAX
+                            && !world.is_structural_counter(claim_index) =>
+                    {
+                        MoveReason::IneffectiveCounter
+                    }
+                    FaultSolverResponse::Skip(_) => MoveReason::SupportsLocalOpinion,
+                    // The direction of the response is in the game's convention, and is
+                    // mapped back to the canonical convention.
+                    FaultSolverResponse::Move(is_attack, ..)
+                    | FaultSolverResponse::Step(is_attack, ..)
+                        if self.convention.orient(*is_attack) =>
+                    {
+                        MoveReason::Disagrees
+                    }
+                    FaultSolverResponse::Move(..) | FaultSolverResponse::Step(..) => {
+                        MoveReason::Agrees
+                    }
+                    FaultSolverResponse::Defer(_) => MoveReason::ProviderUnavailable,
+                    FaultSolverResponse::Resolve(_) => MoveReason::Resolvable,
+                };
+
+                MoveReport {
+                    observed_claim: claim.value,
+                    honest_claim: self.inner.state_hash(claim.position).ok(),
+                    reason,
+                    response,
+                }
+            })
+            .collect())
+    }
+
+    /// Solves a single claim, as in [DisputeSolver::available_moves], and returns the response
+    /// alongside a [DecisionRecord] for auditing the decision after the fact. The claim solver
+    /// is not aware of the record, so the local opinion of the claim is fetched from the
+    /// provider a second time.
+    ///
+    /// ### Takes
+    /// - `world`: The [FaultDisputeState] to solve against.
+    /// - `claim_index`: The index of the claim within the state DAG.
+    ///
+    /// ### Returns
+    /// - The response and its [DecisionRecord], or an error if the claim could not be solved.
+    pub fn solve_claim_audited(
+        &self,
+        world: &mut FaultDisputeState,
+        claim_index: usize,
+    ) -> anyhow::Result<(FaultSolverResponse<T>, DecisionRecord)> {
+        let claim = *world
+            .state()
+            .get(claim_index)
+            .ok_or(anyhow::anyhow!("Failed to fetch claim from passed state"))?;
+        let response = self
+            .solve_claims(world, &[claim_index])?
+            .pop()
+            .ok_or(anyhow::anyhow!("Claim {} was not solved", claim_index))?;
+
+        let is_attack = match response {
+            FaultSolverResponse::Move(is_attack, ..) | FaultSolverResponse::Step(is_attack, ..) => {
+                Some(is_attack)
+            }
+            FaultSolverResponse::Skip(_)
+            | FaultSolverResponse::Defer(_)
+            | FaultSolverResponse::Resolve(_) => None,
+        };
+        let record = DecisionRecord {
+            claim_index,
+            honest_claim: self.inner.state_hash(claim.position).ok(),
+            opponent_claim: claim.value,
+            depth: claim.position.depth(),
+            is_attack,
+            response: (&response).into(),
+        };
+        Ok((response, record))
+    }
+
+    /// Returns the positions that the provider may be queried for when solving the unvisited
+    /// claims in the passed [FaultDisputeState], without making any provider calls. The
+    /// direction of each move is not known until the provider is queried, so the returned
+    /// positions cover both the attack and the defense against each claim.
+    ///
+    /// ### Takes
+    /// - `world`: The [ClaimDag] of the game to be solved, e.g. a [FaultDisputeState] or a
+    ///   [crate::CompactFaultDisputeState].
+    ///
+    /// ### Returns
+    /// - The sorted, deduplicated positions that a solve pass may query.
+    pub fn required_positions(&self, world: &impl ClaimDag) -> Vec<Position> {
+        let mut positions = vec![Self::ROOT_CLAIM_POSITION];
+
+        (0..world.len())
+            .filter_map(|i| world.claim(i).map(|c| (i, c)))
+            .filter(|(_, c)| !c.visited)
+            .for_each(|(i, claim)| {
+                if claim.parent_index == u32::MAX {
+                    positions.extend([claim.position, claim.position.make_move(true)]);
+                } else if !world.is_structural_counter(i) {
+                    // Ineffective claims are skipped without querying the provider.
+                } else if claim.position.depth() == world.max_depth() {
+                    // A step against a leaf requires the pre-state to the left of the claim
+                    // when attacking, or at the claim's position when defending.
+                    positions.push(claim.position);
+                    if !matches!(claim.position.index_at_depth(), Ok(0)) {
+                        positions.push(claim.position - 1);
+                    }
+                } else {
+                    positions.extend([
+                        claim.position,
+                        claim.position.make_move(true),
+                        claim.position.make_move(false),
+                    ]);
+                }
+            });
+
+        positions.sort_unstable();
+        positions.dedup();
+        positions
+    }
+
+    /// Predicts the next move of a rational opponent by running the claim solver from the
+    /// opponent's perspective. The opponent is assumed to share the local trace below the
+    /// root, so only its opinion of the root claim differs. The opponent responds to the most
+    /// recent claim it opposes, and moves to positions that are already occupied are not
+    /// predicted.
+    ///
+    /// ### Takes
+    /// - `world`: The [FaultDisputeState] to predict against.
+    /// - `opponent_opinion`: The opponent's opinion of the root claim.
+    ///
+    /// ### Returns
+    /// - The predicted move or step, or [None] if the opponent has nothing to respond to.
+    pub fn predict_opponent_move(
+        &self,
+        world: &FaultDisputeState,
+        opponent_opinion: Claim,
+    ) -> anyhow::Result<Option<FaultSolverResponse<T>>> {
+        let attacking_root = opponent_opinion != world.root_claim();
+        for claim_index in (0..world.state().len()).rev() {
+            let claim = &world.state()[claim_index];
+            if claim.parent_index != u32::MAX && !world.is_structural_counter(claim_index) {
+                continue;
+            }
+
+            match self.inner.solve_claim(world, claim_index, attacking_root)? {
+                FaultSolverResponse::Move(is_attack, ..)
+                    if world.state().iter().any(|c| {
+                        c.parent_index as usize == claim_index
+                            && c.position == claim.position.make_move(is_attack)
+                    }) => {}
+                response @ (FaultSolverResponse::Move(..) | FaultSolverResponse::Step(..)) => {
+                    return Ok(Some(self.orient_response(response)))
+                }
+                _ => {}
+            }
+        }
+        Ok(None)
+    }
+
+    /// Finds the first trace index at which an opponent's trace diverges from the local trace
+    /// by bisecting over the leaves of the position tree. Once the traces diverge, they are
+    /// assumed to remain diverged, so only `O(log n)` state hashes are fetched from each
+    /// provider.
+    ///
+    /// ### Takes
+    /// - `opponent`: The [TraceProvider] serving the opponent's trace.
+    /// - `max_depth`: The max depth of the position tree.
+    ///
+    /// ### Returns
+    /// - `u64` or [Err]: The first divergent trace index, or an error if the traces do not
+    ///   diverge or a provider fails.
+    pub fn find_divergence<U, O>(&self, opponent: &O, max_depth: u8) -> anyhow::Result<u64>
+    where
+        U: AsRef<[u8]>,
+        O: TraceProvider<U>,
+    {
+        let diverges = |trace_index: u64| -> anyhow::Result<bool> {
+            let position = compute_gindex(max_depth, trace_index);
+            Ok(self.inner.state_hash(position)? != opponent.state_hash(position)?)
+        };
+
+        let trace_len = 1u64
+            .checked_shl(max_depth as u32)
+            .ok_or(anyhow::anyhow!("Max depth {} is too deep", max_depth))?;
+        if !diverges(trace_len - 1)? {
+            anyhow::bail!("Traces do not diverge");
+        }
+
+        // The first divergent trace index is always within `[low, high]`.
+        let (mut low, mut high) = (0, trace_len - 1);
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if diverges(mid)? {
+                high = mid;
+            } else {
+                low = mid + 1;
+            }
+        }
+        Ok(low)
+    }
+
+    /// Checks that the max depth advertised by the provider matches the max depth of the
+    /// game. Trace indices are computed against the provider's max depth, so a mismatch would
+    /// silently solve against the wrong trace indices. Providers that do not advertise a max
+    /// depth are not checked.
+    ///
+    /// ### Takes
+    /// - `world`: The [ClaimDag] of the game to be solved.
+    ///
+    /// ### Returns
+    /// - `()` or [Err]: An error if the provider's max depth differs from the game's.
+    pub fn validate_config(&self, world: &impl ClaimDag) -> anyhow::Result<()> {
+        match self.provider().max_depth() {
+            Some(max_depth) if max_depth != world.max_depth() => anyhow::bail!(
+                "Provider max depth {} does not match the game max depth {}",
+                max_depth,
+                world.max_depth()
+            ),
+            _ => Ok(()),
+        }
+    }
+
+    /// Checks that the absolute prestate hash computed by the provider matches the absolute
+    /// prestate committed to by the game's contract. A mismatch means that the provider was
+    /// configured with the wrong prestate, and that every step it serves would fail.
+    ///
+    /// ### Takes
+    /// - `onchain_prestate`: The absolute prestate hash returned by the contract's
+    ///   `absolutePrestate` getter.
+    ///
+    /// ### Returns
+    /// - `()` or [Err]: An error if the provider's absolute prestate hash differs.
+    pub fn validate_absolute_prestate(&self, onchain_prestate: Claim) -> anyhow::Result<()> {
+        let local_prestate = self.provider().absolute_prestate_hash();
+        if local_prestate != onchain_prestate {
+            anyhow::bail!(
+                "Provider absolute prestate {} does not match the on-chain absolute prestate {}",
+                local_prestate,
+                onchain_prestate
+            );
+        }
+        Ok(())
+    }
+
+    /// Checks that the local opinion of the root claim matches the root claim of a game that
+    /// is believed to be honest. A mismatch usually means that the provider's trace is not
+    /// aligned with the game, e.g. because it starts from the wrong block, and that every
+    /// move it computes would be wrong.
+    ///
+    /// ### Takes
+    /// - `expected_root`: The root claim of the game.
+    ///
+    /// ### Returns
+    /// - `()` or [Err]: An error if the local opinion of the root claim differs, or if the
+    ///   provider fails.
+    pub fn sanity_check_alignment(&self, expected_root: Claim) -> anyhow::Result<()> {
+        let honest_root = self.inner.state_hash(Self::ROOT_CLAIM_POSITION)?;
+        if honest_root != expected_root {
+            anyhow::bail!(
+                "Provider misaligned: honest root {} != game root {}",
+                honest_root,
+                expected_root
+            );
+        }
+        Ok(())
+    }
+
+    /// Returns the indices of all unvisited claims within the world DAG.
+    fn unvisited_indices(world: &FaultDisputeState) -> Vec<usize> {
+        world
+            .state()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| (!c.visited).then_some(i))
+            .collect()
+    }
+
+    /// Solves each of the claims at the passed indices in order, setting their visited flags.
+    /// The returned responses are in the same order as the passed indices.
+    fn solve_claims(
+        &self,
+        game: &mut FaultDisputeState,
+        claim_indices: &[usize],
+    ) -> anyhow::Result<Vec<FaultSolverResponse<T>>> {
+        let attacking_root = self.attacking_root(game)?;
+        self.solve_claims_until(game, claim_indices, attacking_root, None)
+    }
+
+    /// Validates the solver's configuration against the state, and fetches the local opinion
+    /// on the root claim.
+    ///
+    /// ### Returns
+    /// - `bool` or [Err]: Whether or not the solver is attacking the root claim.
+    fn attacking_root(&self, game: &FaultDisputeState) -> anyhow::Result<bool> {
+        self.validate_config(game)?;
+
+        let _span =
+            tracing::debug_span!("provider.state_hash", position = %Self::ROOT_CLAIM_POSITION)
+                .entered();
+        Ok(self.inner.state_hash(Self::ROOT_CLAIM_POSITION)? != game.root_claim())
+    }
+
+    /// Solves each of the claims at the passed indices in order, setting their visited flags,
+    /// until the deadline passes. The returned responses are for a prefix of the passed
+    /// indices, in the same order.
+    fn solve_claims_until(
+        &self,
+        game: &mut FaultDisputeState,
+        claim_indices: &[usize],
+        attacking_root: bool,
+        deadline: Option<Instant>,
+    ) -> anyhow::Result<Vec<FaultSolverResponse<T>>> {
+        let mut visited = game.state().iter().map(|c| c.visited).collect::<Vec<_>>();
+        let responses =
+            self.solve_claims_tracked(game, claim_indices, &mut visited, attacking_root, deadline);
+        game.state_mut()
+            .iter_mut()
+            .zip(visited)
+            .for_each(|(claim, visited)| claim.visited = visited);
+        responses
+    }
+
+    /// Solves each of the claims at the passed indices in order until the deadline passes,
+    /// without mutating the state. The claims that are solved are marked in `visited`, which
+    /// holds a flag for each claim in the state DAG. Claims that are deferred are left
+    /// unmarked, so that they are solved again in a later pass. The returned responses are for
+    /// a prefix of the passed indices, in the same order.
+    fn solve_claims_tracked(
+        &self,
+        game: &FaultDisputeState,
+        claim_indices: &[usize],
+        visited: &mut [bool],
+        attacking_root: bool,
+        deadline: Option<Instant>,
+    ) -> anyhow::Result<Vec<FaultSolverResponse<T>>> {
+        // Solve each claim, set the visited flag, and return the responses.
+        claim_indices
+            .iter()
+            .take_while(|_| deadline.is_none_or(|deadline| Instant::now() < deadline))
+            .map(|claim_index| {
+                let claim = &game.state()[*claim_index];
+                if !claim.position.is_valid() {
+                    anyhow::bail!(
+                        "Claim {} has invalid position {}",
+                        claim_index,
+                        claim.position
+                    );
+                }
+                let depth = claim.position.depth();
+                let _span = tracing::debug_span!("solve_claim", claim_index, depth).entered();
+
+                // The parent of a claim may not be loaded yet if the state is being loaded
+                // incrementally. The claim is left unvisited, to be solved once it is.
+                if claim.parent_index != u32::MAX
+                    && claim.parent_index as usize >= game.state().len()
+                {
+                    return Ok(FaultSolverResponse::Defer(*claim_index));
+                }
+
+                // The solver must not counter its own claims.
+                if self
+                    .address
+                    .is_some_and(|address| claim.claimant == address)
+                {
+                    visited[*claim_index] = true;
+                    return Ok(FaultSolverResponse::Skip(*claim_index));
+                }
+
+                // Claims that do not counter their parent cannot affect the outcome of the
+                // game, and do not need to be countered. They are only ever made by a faulty or
+                // adversarial claimant, so they are logged.
+                if claim.parent_index != u32::MAX && !game.is_structural_counter(*claim_index) {
+                    tracing::warn!(
+                        claim_index,
+                        parent_index = claim.parent_index,
+                        position = %claim.position,
+                        "Skipping a claim that does not counter its parent"
+                    );
+                    visited[*claim_index] = true;
+                    return Ok(FaultSolverResponse::Skip(*claim_index));
+                }
+
+                // Subgames that have already been resolved are settled, and countering their
+                // claims has no effect.
+                if game.is_subgame_resolved(*claim_index) {
+                    visited[*claim_index] = true;
+                    return Ok(FaultSolverResponse::Skip(*claim_index));
+                }
+
+                let response = self.inner.solve_claim(game, *claim_index, attacking_root);
+                visited[*claim_index] = response.is_ok();
+                match response {
+                    // Only a failure to fetch the local opinion is worth retrying. Any other error
+                    // is in the state being solved, and fails the pass.
+                    Err(e) if self.defer_unavailable && e.is::<ProviderError>() => {
+                        tracing::warn!(error = %e, claim_index, "Deferring claim");
+                        Ok(FaultSolverResponse::Defer(*claim_index))
+                    }
+                    res => res.and_then(|response| {
+                        game.check_response_depth(&response)?;
+                        self.verify_response(&response, game);
+                        Ok(self.orient_response(response))
+                    }),
+                }
+            })
+            .collect()
+    }
+
+    /// Maps the direction of a move or step computed by the claim solver from the canonical
+    /// convention onto the game's [BisectionConvention].
+    fn orient_response(&self, response: FaultSolverResponse<T>) -> FaultSolverResponse<T> {
+        match response {
+            FaultSolverResponse::Move(is_attack, claim_index, claim) => {
+                FaultSolverResponse::Move(self.convention.orient(is_attack), claim_index, claim)
+            }
+            FaultSolverResponse::Step(is_attack, claim_index, state_data, proof) => {
+                FaultSolverResponse::Step(
+                    self.convention.orient(is_attack),
+                    claim_index,
+                    state_data,
+                    proof,
+                )
+            }
+            response => response,
+        }
+    }
+
+    /// Verifies that a response computed by the claim solver is consistent with the local
+    /// opinion of the claim it counters. Attacks must counter a claim that disagrees with the
+    /// local opinion, defenses must counter a claim that agrees with it, and the claim posted
+    /// by a move must be the local opinion at the move's position. The VM status byte is only
+    /// ignored between the root and the max depth, as in [claims_equal_ignoring_status].
+    ///
+    /// This check catches logic inversions in claim solvers during development, and is a
+    /// no-op in release builds.
+    fn verify_response(&self, response: &FaultSolverResponse<T>, world: &FaultDisputeState) {
+        if !cfg!(debug_assertions) {
+            return;
+        }
+
+        let (is_attack, claim_index, posted_claim) = match response {
+            FaultSolverResponse::Move(is_attack, claim_index, claim) => {
+                (*is_attack, *claim_index, Some(claim))
+            }
+            FaultSolverResponse::Step(is_attack, claim_index, _, _) => {
+                (*is_attack, *claim_index, None)
+            }
+            _ => return,
+        };
+
+        // If the provider is unavailable, the response cannot be verified.
+        let claim = &world.state()[claim_index];
+        let Ok(local_claim) = self.inner.state_hash(claim.position) else {
+            return;
+        };
+        let depth = claim.position.depth();
+        let disagrees = if depth == 0 || depth == world.max_depth {
+            local_claim != claim.value
+        } else {
+            !claims_equal_ignoring_status(&local_claim, &claim.value)
+        };
+        debug_assert_eq!(
+            is_attack, disagrees,
+            "Response against claim {claim_index} has the wrong direction"
+        );
+
+        if let Some(posted_claim) = posted_claim {
+            let move_position = claim.position.make_move(is_attack);
+            if let Ok(local_move_claim) = self.inner.state_hash(move_position) {
+                debug_assert_eq!(
+                    *posted_claim, local_move_claim,
+                    "Response against claim {claim_index} does not post the local opinion"
+                );
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        providers::{AlphabetTraceProvider, FnTraceProvider, LatencyProvider},
+        AlphaClaimSolver, ClaimData,
+    };
+    use crate::{state::alphabet_claim, AuditedResponse, FixtureTraceProvider};
+    use alloy_primitives::{hex, Address, Bytes};
+    use durin_primitives::{Claim, GameStatus};
+    use serde::Deserialize;
+    use std::{cell::RefCell, collections::HashMap, fs::File, path::Path, time::Duration};
+
+    /// A [TraceProvider] that serves the alphabet trace, but fails to fetch the state
+    /// hash at a single position.
+    struct FlakyProvider {
+        inner: AlphabetTraceProvider,
+        unavailable: Position,
+    }
+
+    impl TraceProvider<[u8; 1]> for FlakyProvider {
+        fn absolute_prestate(&self) -> Arc<[u8; 1]> {
+            self.inner.absolute_prestate()
+        }
+
+        fn absolute_prestate_hash(&self) -> Claim {
+            self.inner.absolute_prestate_hash()
+        }
+
+        fn state_at(&self, position: Position) -> anyhow::Result<Arc<[u8; 1]>> {
+            self.inner.state_at(position)
+        }
+
+        fn state_hash(&self, position: Position) -> anyhow::Result<Claim> {
+            if position == self.unavailable {
+                anyhow::bail!("provider unavailable");
+            }
+            self.inner.state_hash(position)
+        }
+
+        fn proof_at(&self, position: Position) -> anyhow::Result<Arc<[u8]>> {
+            self.inner.proof_at(position)
+        }
+
+        fn max_depth(&self) -> Option<u8> {
+            self.inner.max_depth()
+        }
+    }
+
+    #[test]
+    fn available_moves_defer_unavailable() {
+        let provider = FlakyProvider {
+            inner: AlphabetTraceProvider::new(b'a', 4),
+            unavailable: Position::from(4),
+        };
+        let mut solver = FaultDisputeSolver::new(AlphaClaimSolver::new(provider));
+        let root_claim = Claim::from_slice(&hex!(
+            "c0ffee00c0de0000000000000000000000000000000000000000000000000000"
+        ));
+        let mut state = FaultDisputeState::new(
+            vec![
+                ClaimData {
+                    parent_index: u32::MAX,
+                    visited: false,
+                    value: root_claim,
+                    position: Position::from(1),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 0,
+                    visited: false,
+                    value: root_claim,
+                    position: Position::from(2),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 1,
+                    visited: false,
+                    value: root_claim,
+                    position: Position::from(4),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        // Without deferral, the unavailable position fails the whole pass.
+        assert!(solver.available_moves(&mut state.clone()).is_err());
+
+        solver.defer_unavailable = true;
+        let moves = solver.available_moves(&mut state).unwrap();
+        assert_eq!(
+            &[
+                FaultSolverResponse::Move(
+                    true,
+                    0,
+                    solver.provider().state_hash(Position::from(2)).unwrap()
+                ),
+                FaultSolverResponse::Skip(1),
+                FaultSolverResponse::Defer(2),
+            ],
+            moves.as_ref()
+        );
+
+        // The deferred claim remains unvisited so that it is solved in the next pass.
+        assert!(!state.state()[2].visited);
+    }
+
+    /// A [FaultClaimSolver] that rejects every claim as malformed.
+    struct MalformedSolver(AlphaClaimSolver<[u8; 1], AlphabetTraceProvider>);
+
+    impl FaultClaimSolver<[u8; 1], AlphabetTraceProvider> for MalformedSolver {
+        fn solve_claim(
+            &self,
+            _: &FaultDisputeState,
+            claim_index: usize,
+            _: bool,
+        ) -> anyhow::Result<FaultSolverResponse<[u8; 1]>> {
+            anyhow::bail!("Claim {} is malformed", claim_index)
+        }
+
+        fn provider(&self) -> &AlphabetTraceProvider {
+            self.0.provider()
+        }
+    }
+
+    #[test]
+    fn available_moves_defer_only_unavailable() {
+        let mut solver = FaultDisputeSolver::new(MalformedSolver(AlphaClaimSolver::new(
+            AlphabetTraceProvider::new(b'a', 4),
+        )));
+        solver.defer_unavailable = true;
+        let mut state = FaultDisputeState::new(
+            vec![alphabet_claim(u32::MAX, Position::ROOT, Claim::ZERO)],
+            Claim::ZERO,
+            GameStatus::InProgress,
+            4,
+        );
+
+        // Errors that are not raised by the provider are not deferred.
+        assert_eq!(
+            solver.available_moves(&mut state).unwrap_err().to_string(),
+            "Claim 0 is malformed"
+        );
+        assert!(!state.state()[0].visited);
+    }
+
+    /// A [FaultClaimSolver] that inverts the direction of the moves of the [AlphaClaimSolver].
+    struct InvertedSolver(AlphaClaimSolver<[u8; 1], AlphabetTraceProvider>);
+
+    impl FaultClaimSolver<[u8; 1], AlphabetTraceProvider> for InvertedSolver {
+        fn solve_claim(
+            &self,
+            world: &FaultDisputeState,
+            claim_index: usize,
+            attacking_root: bool,
+        ) -> anyhow::Result<FaultSolverResponse<[u8; 1]>> {
+            match self.0.solve_claim(world, claim_index, attacking_root)? {
+                FaultSolverResponse::Move(is_attack, claim_index, claim) => {
+                    Ok(FaultSolverResponse::Move(!is_attack, claim_index, claim))
+                }
+                response => Ok(response),
+            }
+        }
+
+        fn provider(&self) -> &AlphabetTraceProvider {
+            self.0.provider()
+        }
+    }
+
+    /// A [FaultClaimSolver] that bisects further rather than stepping against leaf claims.
+    struct OvershootSolver(AlphaClaimSolver<[u8; 1], AlphabetTraceProvider>);
+
+    impl FaultClaimSolver<[u8; 1], AlphabetTraceProvider> for OvershootSolver {
+        fn solve_claim(
+            &self,
+            world: &FaultDisputeState,
+            claim_index: usize,
+            attacking_root: bool,
+        ) -> anyhow::Result<FaultSolverResponse<[u8; 1]>> {
+            match self.0.solve_claim(world, claim_index, attacking_root)? {
+                FaultSolverResponse::Step(is_attack, claim_index, ..) => Ok(
+                    FaultSolverResponse::Move(is_attack, claim_index, Claim::ZERO),
+                ),
+                response => Ok(response),
+            }
+        }
+
+        fn provider(&self) -> &AlphabetTraceProvider {
+            self.0.provider()
+        }
+    }
+
+    #[test]
+    fn overshooting_move_rejected() {
+        let provider = AlphabetTraceProvider::new(b'a', 4);
+        let solver = FaultDisputeSolver::new(OvershootSolver(AlphaClaimSolver::new(provider)));
+        let honest = |position| solver.provider().state_hash(position).unwrap();
+        let bad_claim = Claim::repeat_byte(0xFF);
+        let claim = |parent_index, position, value| ClaimData {
+            visited: true,
+            ..alphabet_claim(parent_index, position, value)
+        };
+        let mut state = FaultDisputeState::new(
+            vec![
+                claim(u32::MAX, Position::from(1), bad_claim),
+                claim(0, Position::from(2), honest(Position::from(2))),
+                claim(1, Position::from(4), bad_claim),
+                claim(2, Position::from(8), honest(Position::from(8))),
+                ClaimData {
+                    visited: false,
+                    ..claim(3, Position::from(16), bad_claim)
+                },
+            ],
+            bad_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        assert_eq!(
+            solver.available_moves(&mut state).unwrap_err().to_string(),
+            "Move against claim 4 to position 32 exceeds the max depth 4"
+        );
+        assert_eq!(
+            state
+                .check_response_depth(&FaultSolverResponse::<[u8; 1]>::Step(
+                    true,
+                    3,
+                    Arc::new([b'a']),
+                    Arc::new([])
+                ))
+                .unwrap_err()
+                .to_string(),
+            "Step against claim 3 at depth 3 is not at the max depth 4"
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Response against claim 1 has the wrong direction")]
+    fn available_moves_verify_inverted_response() {
+        let provider = AlphabetTraceProvider::new(b'a', 4);
+        let solver = FaultDisputeSolver::new(InvertedSolver(AlphaClaimSolver::new(provider)));
+        let root_claim = solver.provider().state_hash(Position::from(1)).unwrap();
+        let mut state = FaultDisputeState::new(
+            vec![
+                ClaimData {
+                    parent_index: u32::MAX,
+                    visited: true,
+                    value: root_claim,
+                    position: Position::from(1),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 0,
+                    visited: false,
+                    value: Claim::repeat_byte(0xFF),
+                    position: Position::from(2),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        let _ = solver.available_moves(&mut state);
+    }
+
+    #[test]
+    fn available_moves_root_differs_in_status() {
+        let provider = AlphabetTraceProvider::new(b'a', 4);
+        let solver = FaultDisputeSolver::new(AlphaClaimSolver::new(provider));
+        let mut root_claim = solver.provider().state_hash(Position::from(1)).unwrap();
+        root_claim[0] ^= 0x01;
+        let mut state = FaultDisputeState::new(
+            vec![alphabet_claim(u32::MAX, Position::from(1), root_claim)],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        // The root's VM status byte is meaningful, so a root that differs from the honest root
+        // only in its status is attacked, and the response passes verification.
+        let moves = solver.available_moves(&mut state).unwrap();
+        assert_eq!(
+            moves[..],
+            [FaultSolverResponse::Move(
+                true,
+                0,
+                solver.provider().state_hash(Position::from(2)).unwrap()
+            )]
+        );
+    }
+
+    /// A [TraceProvider] that records every position it is queried for.
+    struct RecordingProvider {
+        inner: AlphabetTraceProvider,
+        queried: RefCell<Vec<Position>>,
+    }
+
+    impl TraceProvider<[u8; 1]> for RecordingProvider {
+        fn absolute_prestate(&self) -> Arc<[u8; 1]> {
+            self.inner.absolute_prestate()
+        }
+
+        fn absolute_prestate_hash(&self) -> Claim {
+            self.inner.absolute_prestate_hash()
+        }
+
+        fn state_at(&self, position: Position) -> anyhow::Result<Arc<[u8; 1]>> {
+            self.queried.borrow_mut().push(position);
+            self.inner.state_at(position)
+        }
+
+        fn state_hash(&self, position: Position) -> anyhow::Result<Claim> {
+            self.queried.borrow_mut().push(position);
+            self.inner.state_hash(position)
+        }
+
+        fn proof_at(&self, position: Position) -> anyhow::Result<Arc<[u8]>> {
+            self.queried.borrow_mut().push(position);
+            self.inner.proof_at(position)
+        }
+
+        fn max_depth(&self) -> Option<u8> {
+            self.inner.max_depth()
+        }
+    }
+
+    #[test]
+    fn honest_claim_at() {
+        let provider = AlphabetTraceProvider::new(b'a', 4);
+        let solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4)));
+
+        for position in (1..32).map(Position::from) {
+            assert_eq!(
+                solver.honest_claim_at(position).unwrap(),
+                provider.state_hash(position).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn inverted_convention_mirrors_moves() {
+        let solver = |convention| {
+            let mut solver =
+                FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4)));
+            solver.convention = convention;
+            solver
+        };
+        let (canonical, inverted) = (
+            solver(BisectionConvention::OpStack),
+            solver(BisectionConvention::Inverted),
+        );
+        let honest = |position| canonical.provider().state_hash(position).unwrap();
+        let dishonest = Claim::repeat_byte(0xFF);
+        let state = FaultDisputeState::new(
+            vec![
+                alphabet_claim(u32::MAX, Position::from(1), dishonest),
+                alphabet_claim(0, Position::from(2), honest(Position::from(2))),
+                alphabet_claim(1, Position::from(4), dishonest),
+                alphabet_claim(1, Position::from(6), honest(Position::from(6))),
+                alphabet_claim(2, Position::from(8), honest(Position::from(8))),
+                alphabet_claim(4, Position::from(16), dishonest),
+            ],
+            dishonest,
+            GameStatus::InProgress,
+            4,
+        );
+
+        let canonical_moves = canonical.available_moves(&mut state.clone()).unwrap();
+        let inverted_moves = inverted.available_moves(&mut state.clone()).unwrap();
+        assert_eq!(canonical_moves.len(), inverted_moves.len());
+        for (canonical_move, inverted_move) in canonical_moves.iter().zip(inverted_moves.iter()) {
+            match (canonical_move, inverted_move) {
+                (
+                    FaultSolverResponse::Move(a, i, claim),
+                    FaultSolverResponse::Move(b, j, inverted_claim),
+                ) => {
+                    assert_eq!((!a, i, claim), (*b, j, inverted_claim));
+
+                    // Both moves are made to the same position under their conventions.
+                    let position = state.state()[*i].position;
+                    assert_eq!(
+                        BisectionConvention::OpStack.make_move(&position, *a),
+                        BisectionConvention::Inverted.make_move(&position, *b)
+                    );
+                }
+                (
+                    FaultSolverResponse::Step(a, i, state_data, proof),
+                    FaultSolverResponse::Step(b, j, inverted_state_data, inverted_proof),
+                ) => {
+                    assert_eq!(
+                        (!a, i, state_data, proof),
+                        (*b, j, inverted_state_data, inverted_proof)
+                    );
+
+                    let position = state.state()[*i].position;
+                    assert_eq!(
+                        BisectionConvention::OpStack.step_prestate_position(position, *a),
+                        BisectionConvention::Inverted.step_prestate_position(position, *b)
+                    );
+                }
+                (a, b) => assert_eq!(a, b),
+            }
+        }
+        assert!(canonical_moves
+            .iter()
+            .any(|m| matches!(m, FaultSolverResponse::Move(false, ..))));
+        assert!(canonical_moves
+            .iter()
+            .any(|m| matches!(m, FaultSolverResponse::Step(true, ..))));
+    }
+
+    #[test]
+    fn required_positions_cover_solve() {
+        let provider = RecordingProvider {
+            inner: AlphabetTraceProvider::new(b'a', 4),
+            queried: RefCell::new(Vec::new()),
+        };
+        let solver = FaultDisputeSolver::new(AlphaClaimSolver::new(provider));
+        let root_claim = Claim::repeat_byte(0xFF);
+        let honest_claim = |position| solver.provider().inner.state_hash(position).unwrap();
+        let mut state = FaultDisputeState::new(
+            vec![
+                ClaimData {
+                    parent_index: u32::MAX,
+                    visited: false,
+                    value: root_claim,
+                    position: Position::from(1),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 0,
+                    visited: true,
+                    value: honest_claim(Position::from(2)),
+                    position: Position::from(2),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 1,
+                    visited: false,
+                    value: honest_claim(Position::from(4)),
+                    position: Position::from(4),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 2,
+                    visited: false,
+                    value: honest_claim(Position::from(10)),
+                    position: Position::from(10),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 3,
+                    visited: false,
+                    value: root_claim,
+                    position: Position::from(20),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 4,
+                    visited: false,
+                    value: root_claim,
+                    position: Position::from(42),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        let required = solver.required_positions(&state);
+        assert_eq!(
+            required,
+            [1, 2, 4, 8, 10, 19, 20, 22]
+                .iter()
+                .map(|p| Position::from(*p))
+                .collect::<Vec<_>>()
+        );
+        assert!(solver.provider().queried.borrow().is_empty());
+
+        solver.available_moves(&mut state).unwrap();
+        let queried = solver.provider().queried.borrow();
+        assert!(!queried.is_empty());
+        assert!(queried.iter().all(|p| required.contains(p)));
+    }
+
+    #[test]
+    fn resolved_subgames_skipped() {
+        let solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4)));
+        let honest = |position| solver.provider().state_hash(position).unwrap();
+        let root_claim = honest(Position::from(1));
+        let mut state = FaultDisputeState::new(
+            vec![
+                ClaimData {
+                    parent_index: u32::MAX,
+                    visited: false,
+                    value: root_claim,
+                    position: Position::from(1),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 0,
+                    visited: false,
+                    value: Claim::repeat_byte(0xFF),
+                    position: Position::from(2),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+        let mut resolved = state.clone();
+        resolved.mark_subgame_resolved(1);
+
+        let moves = solver.available_moves(&mut state).unwrap();
+        assert_eq!(
+            *moves,
+            [
+                FaultSolverResponse::Skip(0),
+                FaultSolverResponse::Move(true, 1, honest(Position::from(4))),
+            ]
+        );
+
+        // The claims of the resolved subgame produce no moves.
+        let moves = solver.available_moves(&mut resolved).unwrap();
+        assert_eq!(
+            *moves,
+            [FaultSolverResponse::Skip(0), FaultSolverResponse::Skip(1)]
+        );
+        assert!(resolved.state()[1].visited);
+    }
+
+    #[test]
+    fn overrides_skip_provider() {
+        let provider = RecordingProvider {
+            inner: AlphabetTraceProvider::new(b'a', 4),
+            queried: RefCell::new(Vec::new()),
+        };
+        let patched = Claim::repeat_byte(0xAA);
+        let solver = FaultDisputeSolver::new(
+            AlphaClaimSolver::new(provider)
+                .with_overrides(HashMap::from([(Position::from(2), patched)])),
+        );
+        let root_claim = Claim::repeat_byte(0xFF);
+        let mut state = FaultDisputeState::new(
+            vec![ClaimData {
+                parent_index: u32::MAX,
+                visited: false,
+                value: root_claim,
+                position: Position::from(1),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            }],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        let moves = solver.available_moves(&mut state).unwrap();
+        assert_eq!(*moves, [FaultSolverResponse::Move(true, 0, patched)]);
+        assert_eq!(solver.inner.state_hash(Position::from(2)).unwrap(), patched);
+
+        // The overridden position is never fetched from the provider.
+        let queried = solver.provider().queried.borrow();
+        assert!(queried.contains(&Position::from(1)));
+        assert!(!queried.contains(&Position::from(2)));
+    }
+
+    #[test]
+    fn available_moves_ordered_by_claim_index() {
+        // Claims closer to the root are slower to solve than deeper claims.
+        let alphabet = AlphabetTraceProvider::new(b'a', 4);
+        let provider = LatencyProvider::new(
+            FnTraceProvider::new([b'a'], move |position: Position| {
+                std::thread::sleep(Duration::from_millis(4 - position.depth().min(4) as u64));
+                alphabet.state_hash(position)
+            }),
+            Duration::from_millis(1),
+        );
+        let solver = FaultDisputeSolver::new(AlphaClaimSolver::new(provider));
+        let root_claim = Claim::repeat_byte(0xFF);
+        let claim = |parent_index, position| alphabet_claim(parent_index, position, root_claim);
+        let mut state = FaultDisputeState::new(
+            vec![
+                claim(u32::MAX, Position::from(1)),
+                claim(0, Position::from(2)),
+                claim(1, Position::from(4)),
+                claim(1, Position::from(6)),
+                claim(2, Position::from(8)),
+                claim(3, Position::from(12)),
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        let moves = solver.available_moves(&mut state).unwrap();
+        let claim_indices = moves
+            .iter()
+            .map(|m| match m {
+                FaultSolverResponse::Move(_, i, _)
+                | FaultSolverResponse::Skip(i)
+                | FaultSolverResponse::Step(_, i, _, _)
+                | FaultSolverResponse::Defer(i)
+                | FaultSolverResponse::Resolve(i) => *i,
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(claim_indices, vec![0, 1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn available_moves_resolve_expired() {
+        let solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4)));
+        let honest = |position| solver.honest_claim_at(position).unwrap();
+        let claim = |parent_index, position, value| ClaimData {
+            visited: true,
+            claimant: Address::repeat_byte(0x01),
+            ..alphabet_claim(parent_index, position, value)
+        };
+        // The honest root is attacked by a dishonest claim, which is countered by an honest
+        // attack.
+        let mut state = FaultDisputeState::new(
+            vec![
+                claim(u32::MAX, Position::from(1), honest(Position::from(1))),
+                claim(0, Position::from(2), Claim::repeat_byte(0xFF)),
+                claim(1, Position::from(4), honest(Position::from(4))),
+            ],
+            honest(Position::from(1)),
+            GameStatus::InProgress,
+            4,
+        );
+        let expired = state.game_duration;
+
+        // Nothing can be resolved before the clocks expire.
+        assert!(solver.available_moves_at(&mut state, 0).unwrap().is_empty());
+
+        // The honest claim resolves uncountered, in favor of the local opinion.
+        assert_eq!(
+            *solver.available_moves_at(&mut state, expired).unwrap(),
+            [FaultSolverResponse::Resolve(2)]
+        );
+
+        // Once the honest claim's subgame is resolved, the fully countered subgame of the
+        // dishonest claim can be resolved.
+        state.mark_subgame_resolved(2);
+        assert_eq!(
+            *solver.available_moves_at(&mut state, expired).unwrap(),
+            [FaultSolverResponse::Resolve(1)]
+        );
+
+        // Resolving an uncountered dishonest claim is not in favor of the local opinion.
+        let mut state = FaultDisputeState::new(
+            state.state()[..2].to_vec(),
+            honest(Position::from(1)),
+            GameStatus::InProgress,
+            4,
+        );
+        assert!(solver
+            .available_moves_at(&mut state, expired)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn available_moves_defer_missing_parent() {
+        let solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4)));
+        let root_claim = Claim::repeat_byte(0xFF);
+        let claim = |parent_index, position| alphabet_claim(parent_index, position, root_claim);
+        // Claim 2 references claim 3, which has not been loaded yet.
+        let mut state = FaultDisputeState::new(
+            vec![
+                claim(u32::MAX, Position::from(1)),
+                claim(0, Position::from(2)),
+                claim(3, Position::from(8)),
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+        let moves = solver.available_moves(&mut state).unwrap();
+        assert_eq!(
+            moves.iter().map(|r| r.claim_index()).collect::<Vec<_>>(),
+            vec![0, 1, 2]
+        );
+        assert_eq!(moves[2], FaultSolverResponse::Defer(2));
+        assert!(!state.state()[2].visited);
+
+        // Once the missing parent is loaded, both claims are solved as if the state had been
+        // fully loaded from the start.
+        state.state_mut().push(claim(1, Position::from(4)));
+        let mut fresh = state.clone();
+        fresh.state_mut().iter_mut().for_each(|c| c.visited = false);
+        let expected = solver.available_moves(&mut fresh).unwrap();
+
+        let moves = solver.available_moves(&mut state).unwrap();
+        assert_eq!(*moves, expected[2..]);
+        assert!(!moves
+            .iter()
+            .any(|r| matches!(r, FaultSolverResponse::Defer(_))));
+        assert!(state.state().iter().all(|c| c.visited));
+    }
+
+    #[test]
+    fn available_moves_skip_own_claims() {
+        let address = Address::repeat_byte(0x01);
+        let mut solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4)));
+        let root_claim = Claim::repeat_byte(0xFF);
+        let claim = |parent_index, position, claimant| ClaimData {
+            claimant,
+            ..alphabet_claim(parent_index, position, root_claim)
+        };
+        // The solver made the dishonest claim 2, which it would otherwise attack.
+        let state = FaultDisputeState::new(
+            vec![
+                claim(u32::MAX, Position::from(1), Address::ZERO),
+                claim(0, Position::from(2), Address::ZERO),
+                claim(1, Position::from(4), address),
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        let moves = solver.available_moves(&mut state.clone()).unwrap();
+        assert!(matches!(moves[2], FaultSolverResponse::Move(true, 2, _)));
+
+        solver.address = Some(address);
+        let mut own = state.clone();
+        let moves = solver.available_moves(&mut own).unwrap();
+        assert_eq!(moves[2], FaultSolverResponse::Skip(2));
+        assert!(own.state()[2].visited);
+    }
+
+    #[test]
+    fn available_moves_readonly() {
+        let solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4)));
+        let root_claim = Claim::repeat_byte(0xFF);
+        let honest = |position| solver.provider().state_hash(position).unwrap();
+        let mut state = FaultDisputeState::new(
+            vec![
+                alphabet_claim(u32::MAX, Position::from(1), root_claim),
+                alphabet_claim(0, Position::from(2), honest(Position::from(2))),
+                alphabet_claim(1, Position::from(4), root_claim),
+                alphabet_claim(2, Position::from(8), honest(Position::from(8))),
+                alphabet_claim(3, Position::from(16), root_claim),
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+        state.state_mut()[1].visited = true;
+        let snapshot = state.clone();
+
+        let readonly = solver.available_moves_readonly(&state).unwrap();
+        assert_eq!(
+            state.state(),
+            snapshot.state(),
+            "The readonly pass must not mutate the state"
+        );
+        assert_eq!(readonly, solver.available_moves(&mut state).unwrap());
+        assert_eq!(
+            readonly.iter().map(|r| r.claim_index()).collect::<Vec<_>>(),
+            vec![0, 2, 3, 4]
+        );
+    }
+
+    #[test]
+    fn available_moves_with_deadline() {
+        let delay = Duration::from_millis(50);
+        let solver = FaultDisputeSolver::new(AlphaClaimSolver::new(LatencyProvider::new(
+            AlphabetTraceProvider::new(b'a', 4),
+            delay,
+        )));
+        let root_claim = Claim::repeat_byte(0xFF);
+        let claim = |parent_index, position| alphabet_claim(parent_index, position, root_claim);
+        let mut state = FaultDisputeState::new(
+            vec![
+                claim(u32::MAX, Position::from(1)),
+                claim(0, Position::from(2)),
+                claim(1, Position::from(4)),
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        // Fetching the local opinion of the root claim exhausts the deadline.
+        let moves = solver
+            .available_moves_with_deadline(&mut state, Instant::now() + delay / 2)
+            .unwrap();
+        assert!(moves.responses.is_empty());
+        assert_eq!(moves.timed_out, vec![0, 1, 2]);
+        assert!(state.state().iter().all(|c| !c.visited));
+
+        // The deadline passes while solving the root claim, which still completes.
+        let moves = solver
+            .available_moves_with_deadline(&mut state, Instant::now() + delay * 3 / 2)
+            .unwrap();
+        assert_eq!(moves.responses.len(), 1);
+        assert_eq!(moves.responses[0].claim_index(), 0);
+        assert_eq!(moves.timed_out, vec![1, 2]);
+
+        // The claims that timed out are solved by the next pass.
+        let moves = solver
+            .available_moves_with_deadline(&mut state, Instant::now() + delay * 100)
+            .unwrap();
+        assert_eq!(
+            moves
+                .responses
+                .iter()
+                .map(|r| r.claim_index())
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(moves.timed_out.is_empty());
+    }
+
+    #[test]
+    fn available_moves_mark_visited_where() {
+        let solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4)));
+        let root_claim = Claim::repeat_byte(0xFF);
+        let claim = |parent_index, position| alphabet_claim(parent_index, position, root_claim);
+        let mut state = FaultDisputeState::new(
+            vec![
+                claim(u32::MAX, Position::from(1)),
+                claim(0, Position::from(2)),
+                claim(1, Position::from(4)),
+                claim(2, Position::from(8)),
+                claim(3, Position::from(16)),
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        // Exclude all claims below depth 2 from the solve.
+        state.mark_visited_where(|c| c.position.depth() > 2);
+
+        let moves = solver.available_moves(&mut state).unwrap();
+        assert_eq!(
+            &[
+                FaultSolverResponse::Move(
+                    true,
+                    0,
+                    solver.provider().state_hash(Position::from(2)).unwrap()
+                ),
+                FaultSolverResponse::Skip(1),
+                FaultSolverResponse::Move(
+                    true,
+                    2,
+                    solver.provider().state_hash(Position::from(8)).unwrap()
+                ),
+            ],
+            moves.as_ref()
+        );
+    }
+
+    #[test]
+    fn available_moves_skip_ineffective() {
+        let solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4)));
+        let root_claim = Claim::repeat_byte(0xFF);
+        let mut state = FaultDisputeState::new(
+            vec![
+                ClaimData {
+                    parent_index: u32::MAX,
+                    visited: true,
+                    value: root_claim,
+                    position: Position::from(1),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 0,
+                    visited: true,
+                    value: solver.provider().state_hash(Position::from(2)).unwrap(),
+                    position: Position::from(2),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                // A claim at the position of an attack against the root rather than claim 1.
+                ClaimData {
+                    parent_index: 1,
+                    visited: false,
+                    value: root_claim,
+                    position: Position::from(2),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                // An effective attack against claim 1.
+                ClaimData {
+                    parent_index: 1,
+                    visited: false,
+                    value: root_claim,
+                    position: Position::from(4),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        let moves = solver.available_moves(&mut state).unwrap();
+        assert_eq!(
+            &[
+                FaultSolverResponse::Skip(2),
+                FaultSolverResponse::Move(
+                    true,
+                    3,
+                    solver.provider().state_hash(Position::from(8)).unwrap()
+                ),
+            ],
+            moves.as_ref()
+        );
+        assert!(state.state()[2].visited);
+    }
+
+    #[test]
+    fn available_moves_incremental() {
+        let solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4)));
+        let root_claim = Claim::repeat_byte(0xFF);
+        let previous = FaultDisputeState::new(
+            vec![
+                ClaimData {
+                    parent_index: u32::MAX,
+                    visited: true,
+                    value: root_claim,
+                    position: Position::from(1),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 0,
+                    visited: true,
+                    value: solver.provider().state_hash(Position::from(2)).unwrap(),
+                    position: Position::from(2),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        // The opponent both attacks and defends against the honest claim.
+        let mut state = previous.clone();
+        state.state_mut().extend([
+            ClaimData {
+                parent_index: 1,
+                visited: false,
+                value: root_claim,
+                position: Position::from(4),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            },
+            ClaimData {
+                parent_index: 1,
+                visited: false,
+                value: root_claim,
+                position: Position::from(6),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            },
+        ]);
+        let diff = state.diff(&previous);
+        assert_eq!(diff.countered, vec![1]);
+
+        // The responses match a full pass over a fresh copy of the state, restricted to the
+        // added claims and the newly countered claim.
+        let mut fresh = state.clone();
+        fresh.state_mut().iter_mut().for_each(|c| c.visited = false);
+        let expected = solver
+            .available_moves(&mut fresh)
+            .unwrap()
+            .iter()
+            .filter(|r| r.claim_index() >= 1)
+            .cloned()
+            .collect::<Vec<_>>();
+        let moves = solver
+            .available_moves_incremental(&mut state, &diff)
+            .unwrap();
+        assert_eq!(expected, moves.as_ref());
+        assert_eq!(
+            &[
+                FaultSolverResponse::Skip(1),
+                FaultSolverResponse::Move(
+                    true,
+                    2,
+                    solver.provider().state_hash(Position::from(8)).unwrap()
+                ),
+                FaultSolverResponse::Move(
+                    true,
+                    3,
+                    solver.provider().state_hash(Position::from(12)).unwrap()
+                ),
+            ],
+            moves.as_ref()
+        );
+
+        // Once solved, the same diff only solves the countered claim again.
+        assert_eq!(
+            solver
+                .available_moves_incremental(&mut state, &diff)
+                .unwrap()
+                .as_ref(),
+            &[FaultSolverResponse::Skip(1)]
+        );
+    }
+
+    #[test]
+    fn available_moves_verbose() {
+        let solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4)));
+        let root_claim = Claim::repeat_byte(0xFF);
+        let honest = |position| solver.provider().state_hash(position).unwrap();
+        let mut state = FaultDisputeState::new(
+            vec![
+                ClaimData {
+                    parent_index: u32::MAX,
+                    visited: false,
+                    value: root_claim,
+                    position: Position::from(1),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 0,
+                    visited: false,
+                    value: honest(Position::from(2)),
+                    position: Position::from(2),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                // A claim at the position of an attack against the root rather than claim 1.
+                ClaimData {
+                    parent_index: 1,
+                    visited: false,
+                    value: root_claim,
+                    position: Position::from(2),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                // An attack against claim 1 that agrees with the local opinion.
+                ClaimData {
+                    parent_index: 1,
+                    visited: false,
+                    value: honest(Position::from(4)),
+                    position: Position::from(4),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        let expected = solver.available_moves(&mut state.clone()).unwrap();
+        let reports = solver.available_moves_verbose(&mut state).unwrap();
+        assert_eq!(
+            expected.as_ref(),
+            reports
+                .iter()
+                .map(|r| r.response.clone())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            reports,
+            vec![
+                MoveReport {
+                    response: FaultSolverResponse::Move(true, 0, honest(Position::from(2))),
+                    observed_claim: root_claim,
+                    honest_claim: Some(honest(Position::from(1))),
+                    reason: MoveReason::Disagrees,
+                },
+                MoveReport {
+                    response: FaultSolverResponse::Skip(1),
+                    observed_claim: honest(Position::from(2)),
+                    honest_claim: Some(honest(Position::from(2))),
+                    reason: MoveReason::SupportsLocalOpinion,
+                },
+                MoveReport {
+                    response: FaultSolverResponse::Skip(2),
+                    observed_claim: root_claim,
+                    honest_claim: Some(honest(Position::from(2))),
+                    reason: MoveReason::IneffectiveCounter,
+                },
+                MoveReport {
+                    response: FaultSolverResponse::Move(false, 3, honest(Position::from(10))),
+                    observed_claim: honest(Position::from(4)),
+                    honest_claim: Some(honest(Position::from(4))),
+                    reason: MoveReason::Agrees,
+                },
+            ]
+        );
+        assert!(state.state().iter().all(|c| c.visited));
+    }
+
+    #[test]
+    fn solve_claim_audited() {
+        let solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 1)));
+        let root_claim = solver.provider().state_hash(Position::from(1)).unwrap();
+        let mut state = FaultDisputeState::new(
+            vec![
+                ClaimData {
+                    parent_index: u32::MAX,
+                    visited: false,
+                    value: root_claim,
+                    position: Position::from(1),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                // A faulty leaf claim, which is stepped against.
+                ClaimData {
+                    parent_index: 0,
+                    visited: false,
+                    value: Claim::repeat_byte(0xFF),
+                    position: Position::from(2),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            1,
+        );
+        let expected = solver.available_moves(&mut state.clone()).unwrap();
+
+        let log = (0..state.state().len())
+            .map(|i| {
+                let (response, record) = solver.solve_claim_audited(&mut state, i).unwrap();
+                assert_eq!(response, expected[i]);
+                record
+            })
+            .collect::<Vec<_>>();
+        assert!(state.state().iter().all(|c| c.visited));
+
+        assert_eq!(
+            log[0],
+            DecisionRecord {
+                claim_index: 0,
+                honest_claim: Some(root_claim),
+                opponent_claim: root_claim,
+                depth: 0,
+                is_attack: None,
+                response: AuditedResponse::Skip,
+            }
+        );
+        assert_eq!(
+            log[1],
+            DecisionRecord {
+                claim_index: 1,
+                honest_claim: Some(solver.provider().state_hash(Position::from(2)).unwrap()),
+                opponent_claim: Claim::repeat_byte(0xFF),
+                depth: 1,
+                is_attack: Some(true),
+                response: AuditedResponse::Step {
+                    state_data: Bytes::from_static(b"a"),
+                    proof: Bytes::new(),
+                },
+            }
+        );
+
+        // The log round-trips through JSON.
+        let json = serde_json::to_string(&log).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Vec<DecisionRecord>>(&json).unwrap(),
+            log
+        );
+        assert_eq!(
+            serde_json::to_value(&log[1]).unwrap()["response"]["kind"],
+            "step"
+        );
+
+        assert_eq!(
+            solver
+                .solve_claim_audited(&mut state, 2)
+                .unwrap_err()
+                .to_string(),
+            "Failed to fetch claim from passed state"
+        );
+    }
+
+    #[test]
+    fn zero_position_rejected() {
+        let solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4)));
+        let root_claim = solver.honest_claim_at(Position::from(1)).unwrap();
+        let mut state = FaultDisputeState::new(
+            vec![
+                ClaimData {
+                    parent_index: u32::MAX,
+                    visited: false,
+                    value: root_claim,
+                    position: Position::from(1),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 0,
+                    visited: false,
+                    value: Claim::ZERO,
+                    position: Position::from(0),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        assert!(!Position::from(0).is_valid());
+        assert!(Position::from(1).is_valid());
+        assert_eq!(
+            solver.available_moves(&mut state).unwrap_err().to_string(),
+            "Claim 1 has invalid position 0"
+        );
+    }
+
+    #[test]
+    fn validate_config() {
+        let provider = RecordingProvider {
+            inner: AlphabetTraceProvider::new(b'a', 4),
+            queried: RefCell::new(Vec::new()),
+        };
+        let solver = FaultDisputeSolver::new(AlphaClaimSolver::new(provider));
+        let mut state = FaultDisputeState::new(
+            vec![ClaimData {
+                parent_index: u32::MAX,
+                visited: false,
+                value: Claim::ZERO,
+                position: Position::from(1),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            }],
+            Claim::ZERO,
+            GameStatus::InProgress,
+            5,
+        );
+
+        let err = "Provider max depth 4 does not match the game max depth 5";
+        assert_eq!(solver.validate_config(&state).unwrap_err().to_string(), err);
+        assert_eq!(
+            solver.available_moves(&mut state).unwrap_err().to_string(),
+            err
+        );
+        assert!(solver.provider().queried.borrow().is_empty());
+        assert!(!state.state()[0].visited);
+
+        state.max_depth = 4;
+        assert!(solver.validate_config(&state).is_ok());
+        assert!(solver.available_moves(&mut state).is_ok());
+
+        // Providers that do not advertise a max depth are not checked.
+        let solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(FnTraceProvider::new([b'a'], |_| {
+                Ok(Claim::ZERO)
+            })));
+        state.max_depth = 5;
+        assert!(solver.validate_config(&state).is_ok());
+    }
+
+    #[test]
+    fn validate_absolute_prestate() {
+        let solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4)));
+        let prestate = solver.provider().absolute_prestate_hash();
+        assert!(solver.validate_absolute_prestate(prestate).is_ok());
+        assert_eq!(
+            solver
+                .validate_absolute_prestate(Claim::ZERO)
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "Provider absolute prestate {} does not match the on-chain absolute prestate {}",
+                prestate,
+                Claim::ZERO
+            )
+        );
+    }
+
+    #[test]
+    fn state_hash_at() {
+        let solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4)));
+        for depth in 0..=4 {
+            for index in 0..(1u64 << depth) {
+                let position = Position::from((1 << depth) + index as u128);
+                let expected = solver.provider().state_hash(position).unwrap();
+                assert_eq!(solver.state_hash_at(depth, index).unwrap(), expected);
+                assert_eq!(
+                    solver.provider().state_hash_at(depth, index).unwrap(),
+                    expected
+                );
+            }
+        }
+
+        assert_eq!(
+            solver.state_hash_at(2, 4).unwrap_err().to_string(),
+            "Index 4 is out of bounds at depth 2"
+        );
+        #[cfg(not(feature = "wide-position"))]
+        assert_eq!(
+            solver
+                .provider()
+                .state_hash_at(128, 0)
+                .unwrap_err()
+                .to_string(),
+            "Depth 128 is too deep for a position"
+        );
+    }
+
+    #[test]
+    fn sanity_check_alignment() {
+        let solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4)));
+        let honest_root = solver.honest_claim_at(Position::from(1)).unwrap();
+        assert!(solver.sanity_check_alignment(honest_root).is_ok());
+
+        // A provider whose trace starts one instruction late is misaligned with the game.
+        let misaligned =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'b', 4)));
+        assert_eq!(
+            misaligned
+                .sanity_check_alignment(honest_root)
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "Provider misaligned: honest root {} != game root {}",
+                misaligned.honest_claim_at(Position::from(1)).unwrap(),
+                honest_root
+            )
+        );
+    }
+
+    #[test]
+    fn predict_opponent_move() {
+        let alphabet = || AlphabetTraceProvider::new(b'a', 4);
+        let solver = FaultDisputeSolver::new(AlphaClaimSolver::new(alphabet()));
+        let opponent_opinion = Claim::repeat_byte(0xFF);
+        let challenger = FaultDisputeSolver::new(
+            AlphaClaimSolver::new(alphabet())
+                .with_overrides(HashMap::from([(Position::from(1), opponent_opinion)])),
+        );
+
+        let honest = |position| solver.honest_claim_at(position).unwrap();
+        let claim = |parent_index, position: Position| {
+            alphabet_claim(parent_index, position, honest(position))
+        };
+        let mut state = FaultDisputeState::new(
+            vec![claim(u32::MAX, Position::from(1))],
+            honest(Position::from(1)),
+            GameStatus::InProgress,
+            4,
+        );
+
+        // The challenger attacks the honest root. After the honest response to its attack, it
+        // defends the response. Each prediction matches the challenger's actual move.
+        for (responses, expected) in [
+            (
+                vec![],
+                FaultSolverResponse::Move(true, 0, honest(Position::from(2))),
+            ),
+            (
+                vec![claim(0, Position::from(2)), claim(1, Position::from(6))],
+                FaultSolverResponse::Move(false, 2, honest(Position::from(14))),
+            ),
+        ] {
+            state.state_mut().extend(responses);
+            let predicted = solver
+                .predict_opponent_move(&state, opponent_opinion)
+                .unwrap();
+            assert_eq!(predicted, Some(expected));
+
+            let actual = challenger.available_moves(&mut state.clone()).unwrap();
+            assert!(actual.contains(&predicted.unwrap()));
+        }
+
+        // Once the challenger has moved, the prediction does not repeat its move.
+        state.state_mut().push(claim(2, Position::from(14)));
+        assert!(solver
+            .predict_opponent_move(&state, opponent_opinion)
+            .unwrap()
+            .is_none());
+
+        // An opponent that agrees with the root claim has nothing to respond to.
+        state.state_mut().truncate(1);
+        assert!(solver
+            .predict_opponent_move(&state, honest(Position::from(1)))
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn find_divergence() {
+        for divergence in 0..16 {
+            let provider = RecordingProvider {
+                inner: AlphabetTraceProvider::new(b'a', 4),
+                queried: RefCell::new(Vec::new()),
+            };
+            let solver = FaultDisputeSolver::new(AlphaClaimSolver::new(provider));
+
+            // An opponent whose trace is faulty from the divergent trace index onwards.
+            let alphabet = AlphabetTraceProvider::new(b'a', 4);
+            let opponent = FnTraceProvider::new([b'a'], move |position: Position| {
+                if position.trace_index(4).unwrap() >= divergence {
+                    Ok(Claim::repeat_byte(0xFF))
+                } else {
+                    alphabet.state_hash(position)
+                }
+            });
+
+            assert_eq!(solver.find_divergence(&opponent, 4).unwrap(), divergence);
+            assert!(solver.provider().queried.borrow().len() <= 5);
+        }
+    }
+
+    #[test]
+    fn find_divergence_agreeing_traces() {
+        let solver =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4)));
+        assert_eq!(
+            solver
+                .find_divergence(&AlphabetTraceProvider::new(b'a', 4), 4)
+                .unwrap_err()
+                .to_string(),
+            "Traces do not diverge"
+        );
+    }
+
+    #[test]
+    fn defense_line() {
+        let provider = AlphabetTraceProvider::new(b'a', 4);
+        let root_claim = provider.state_hash(Position::from(1)).unwrap();
+        let solver = FaultDisputeSolver::new(AlphaClaimSolver::new(provider));
+        let mut state = FaultDisputeState::new(
+            vec![ClaimData {
+                parent_index: u32::MAX,
+                visited: false,
+                value: root_claim,
+                position: Position::from(1),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            }],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        // Play out the game against a challenger that attacks every honest claim with a faulty
+        // claim, recording the positions and values of the honest claims.
+        let mut honest = vec![(Position::from(1), root_claim)];
+        while let Some(&(position, _)) = honest.last().filter(|(p, _)| p.depth() < 4) {
+            let parent_index = state.state().len() - 1;
+            state.state_mut().push(ClaimData {
+                parent_index: parent_index as u32,
+                visited: false,
+                value: Claim::repeat_byte(0xFF),
+                position: position.make_move(true),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            });
+
+            let moves = solver.available_moves(&mut state).unwrap();
+            let Some(FaultSolverResponse::Move(is_attack, claim_index, value)) = moves.last()
+            else {
+                panic!("Expected a move against the challenger");
+            };
+            let position = state.state()[*claim_index].position.make_move(*is_attack);
+            state.state_mut().push(ClaimData {
+                parent_index: *claim_index as u32,
+                visited: true,
+                value: *value,
+                position,
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            });
+            honest.push((position, *value));
+        }
+
+        let line = crate::defense_line(4);
+        assert_eq!(
+            line,
+            vec![Position::from(1), Position::from(4), Position::from(16)]
+        );
+        assert_eq!(honest.iter().map(|(p, _)| *p).collect::<Vec<_>>(), line);
+        for (position, value) in honest {
+            assert_eq!(solver.provider().state_hash(position).unwrap(), value);
+        }
+    }
+
+    /// The directory holding the recorded disputes replayed by [replay_fixtures]. Each file in
+    /// the directory is a JSON-encoded [ReplayFixture].
+    const REPLAY_FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/testdata/replays");
+
+    /// A recorded dispute, replayed against the honest solver one snapshot at a time.
+    #[derive(Debug, Deserialize)]
+    struct ReplayFixture {
+        /// The trace fixture backing the honest solver, relative to the replay directory.
+        trace: String,
+        /// The root claim of the game.
+        root_claim: Claim,
+        /// The snapshots of the game, in the order they were observed.
+        steps: Vec<ReplayStep>,
+        /// Whether or not the root claim's subgame resolves countered.
+        root_countered: bool,
+    }
+
+    /// A snapshot of a recorded dispute, and the responses expected from the solver.
+    #[derive(Debug, Deserialize)]
+    struct ReplayStep {
+        /// The claims appended to the game since the previous snapshot.
+        claims: Vec<ReplayClaim>,
+        /// Existing claims that were countered by a step since the previous snapshot, paired
+        /// with the claimant of the step.
+        #[serde(default)]
+        countered: Vec<(usize, Address)>,
+        /// The responses expected from the solver, ordered by claim index.
+        expected: Vec<ReplayResponse>,
+    }
+
+    /// A claim made in a recorded dispute.
+    #[derive(Debug, Deserialize)]
+    struct ReplayClaim {
+        /// The index of the parent claim, or [None] for the root claim.
+        parent_index: Option<u32>,
+        position: u128,
+        value: Claim,
+        claimant: Address,
+        #[serde(default)]
+        clock: u128,
+    }
+
+    /// A response expected from the solver, mirroring [FaultSolverResponse].
+    #[derive(Debug, Deserialize, PartialEq, Eq)]
+    #[serde(tag = "kind", rename_all = "snake_case")]
+    enum ReplayResponse {
+        Move {
+            is_attack: bool,
+            claim_index: usize,
+            value: Claim,
+        },
+        Skip {
+            claim_index: usize,
+        },
+        Step {
+            is_attack: bool,
+            claim_index: usize,
+            state_data: Bytes,
+            proof: Bytes,
+        },
+        Defer {
+            claim_index: usize,
+        },
+        Resolve {
+            claim_index: usize,
+        },
+    }
+
+    impl From<&FaultSolverResponse<Bytes>> for ReplayResponse {
+        fn from(response: &FaultSolverResponse<Bytes>) -> Self {
+            match response {
+                FaultSolverResponse::Move(is_attack, claim_index, value) => Self::Move {
+                    is_attack: *is_attack,
+                    claim_index: *claim_index,
+                    value: *value,
+                },
+                FaultSolverResponse::Skip(claim_index) => Self::Skip {
+                    claim_index: *claim_index,
+                },
+                FaultSolverResponse::Step(is_attack, claim_index, state_data, proof) => {
+                    Self::Step {
+                        is_attack: *is_attack,
+                        claim_index: *claim_index,
+                        state_data: state_data.as_ref().clone(),
+                        proof: proof.to_vec().into(),
+                    }
+                }
+                FaultSolverResponse::Defer(claim_index) => Self::Defer {
+                    claim_index: *claim_index,
+                },
+                FaultSolverResponse::Resolve(claim_index) => Self::Resolve {
+                    claim_index: *claim_index,
+                },
+            }
+        }
+    }
+
+    /// Replays a recorded dispute, asserting that the solver produces exactly the recorded
+    /// responses to each snapshot and that the game resolves as recorded.
+    fn replay(path: &Path) -> anyhow::Result<()> {
+        let fixture: ReplayFixture = serde_json::from_reader(File::open(path)?)?;
+        let provider =
+            FixtureTraceProvider::from_file(path.parent().unwrap_or(path).join(&fixture.trace))?;
+        let max_depth = provider.max_depth;
+        let solver = FaultDisputeSolver::new(AlphaClaimSolver::new(provider));
+        let mut state = FaultDisputeState::new(
+            Vec::new(),
+            fixture.root_claim,
+            GameStatus::InProgress,
+            max_depth,
+        );
+
+        for (i, step) in fixture.steps.iter().enumerate() {
+            state
+                .state_mut()
+                .extend(step.claims.iter().map(|c| ClaimData {
+                    parent_index: c.parent_index.unwrap_or(u32::MAX),
+                    visited: false,
+                    value: c.value,
+                    position: Position::from(c.position),
+                    clock: c.clock,
+                    claimant: c.claimant,
+                    countered_by: Address::ZERO,
+                }));
+            for (index, claimant) in &step.countered {
+                state.state_mut()[*index].countered_by = *claimant;
+            }
+
+            let responses = solver
+                .available_moves(&mut state)?
+                .iter()
+                .map(ReplayResponse::from)
+                .collect::<Vec<_>>();
+            if responses != step.expected {
+                anyhow::bail!(
+                    "Step {}: expected {:?}, got {:?}",
+                    i,
+                    step.expected,
+                    responses
+                );
+            }
+        }
+
+        // Resolve every subgame bottom-up, once all clocks have expired.
+        let now = state.game_duration;
+        let order = state.topological_order()?;
+        for index in order.iter().rev() {
+            state.resolve_subgame(*index, now)?;
+        }
+        let root_countered = order
+            .first()
+            .is_some_and(|root| state.state()[*root].countered_by != Address::ZERO);
+        if root_countered != fixture.root_countered {
+            anyhow::bail!(
+                "Expected root claim countered: {}, got {}",
+                fixture.root_countered,
+                root_countered
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn replay_fixtures() {
+        let mut paths = std::fs::read_dir(REPLAY_FIXTURES)
+            .unwrap()
+            .map(|e| e.unwrap().path())
+            .filter(|p| p.extension().is_some_and(|e| e == "json"))
+            .collect::<Vec<_>>();
+        paths.sort();
+        assert!(!paths.is_empty());
+
+        for path in paths {
+            if let Err(e) = replay(&path) {
+                panic!("Replay of {} failed: {}", path.display(), e);
+            }
+        }
+    }
 }
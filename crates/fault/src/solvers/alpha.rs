@@ -3,20 +3,22 @@
 #![allow(dead_code, unused_variables)]
 
 use crate::{
-    ClaimData, FaultClaimSolver, FaultDisputeGame, FaultDisputeState, FaultSolverResponse, Gindex,
-    Position, TraceProvider,
+    claims_equal_ignoring_status, FaultClaimSolver, FaultDisputeGame, FaultDisputeState,
+    FaultSolverResponse, Gindex, Position, ProviderError, TraceProvider,
 };
 use durin_primitives::Claim;
-use std::{marker::PhantomData, sync::Arc};
+use std::{collections::HashMap, marker::PhantomData, sync::Arc};
 
 /// The alpha claim solver is the first iteration of the Fault dispute game solver used
 /// in the alpha release of the Fault proof system on Optimism.
-struct AlphaClaimSolver<T, P>
+pub struct AlphaClaimSolver<T, P>
 where
     T: AsRef<[u8]>,
     P: TraceProvider<T>,
 {
     provider: P,
+    /// Local opinions that take precedence over the state hashes served by the provider.
+    overrides: HashMap<Position, Claim>,
     _phantom: PhantomData<T>,
 }
 
@@ -36,7 +38,7 @@ where
     /// - [FaultSolverResponse] or [Err]: The best move against the claim.
     fn solve_claim(
         &self,
-        world: &mut FaultDisputeState,
+        world: &FaultDisputeState,
         claim_index: usize,
         attacking_root: bool,
     ) -> anyhow::Result<FaultSolverResponse<T>> {
@@ -45,15 +47,11 @@ where
 
         // Fetch the ClaimData and its position's depth from the world state DAG.
         let claim = world
-            .state_mut()
-            .get_mut(claim_index)
+            .state()
+            .get(claim_index)
             .ok_or(anyhow::anyhow!("Failed to fetch claim from passed state"))?;
         let claim_depth = claim.position.depth();
 
-        // Mark the claim as visited. This mutates the passed state and must be reverted if an
-        // error is thrown.
-        claim.visited = true;
-
         // In the case that the claim's opinion about the root claim is the same as the local
         // opinion, we can skip the claim. It does not matter if this claim is valid or not
         // because it supports the local opinion of the root claim. Countering it would put the
@@ -65,47 +63,47 @@ where
         // If the claim's parent index is `u32::MAX`, it is the root claim. In this case, the only
         // opportunity is to attack if we disagree with the root - there is no other valid move.
         if claim.parent_index == u32::MAX && attacking_root {
-            let claim_hash =
-                Self::fetch_state_hash(&self.provider, claim.position.make_move(true), claim)?;
+            let claim_hash = self.fetch_state_hash(claim.position.make_move(true))?;
             return Ok(FaultSolverResponse::Move(true, claim_index, claim_hash));
         }
 
         // Fetch the local trace provider's opinion of the state hash at the claim's position
-        let self_state_hash = Self::fetch_state_hash(&self.provider, claim.position, claim)?;
+        let self_state_hash = self.fetch_state_hash(claim.position)?;
 
         // TODO(clabby): Consider that because we'll have to search for the pre/post state for the
         // step instruction, we may also need to know if all claims at agreed levels are correct in
         // the path up to the root claim.
 
-        // Determine if the response will be an attack or a defense.
-        let is_attack = self_state_hash != claim.value;
-
-        // If the next move will be at the max depth of the game, then the proper move is to
-        // perform a VM step against the claim. Otherwise, move in the appropriate direction.
+        // If the claim is at the max depth of the game, then the proper move is to perform a VM
+        // step against the claim, if one is warranted.
         if claim_depth == max_depth {
+            let Some(is_attack) = world.needs_step(claim_index, self_state_hash) else {
+                return Ok(FaultSolverResponse::Skip(claim_index));
+            };
             // There is a special case when we are attacking the first leaf claim at the max
             // level where we have to provide the absolute prestate. Otherwise, we can derive
             // the prestate position based off of `is_attack` and the incorrect claim's
             // position.
-            let (pre_state, proof) = if claim.position.index_at_depth() == 0 && is_attack {
-                let pre_state = self.provider.absolute_prestate();
-                // TODO(clabby): There may be a proof for the absolute prestate in Cannon.
-                let proof: Arc<[u8]> = Arc::new([]);
-
-                (pre_state, proof)
-            } else {
-                // If the move is an attack, the pre-state is left of the attacked claim's
-                // position. If the move is a defense, the pre-state for the step is at the
-                // claim's position.
-                //
-                // SAFETY: We can subtract 1 here due to the above check - we will never
-                // underflow the level.
-                let pre_state_pos = claim.position - is_attack as u128;
-
-                let pre_state = Self::fetch_state_at(&self.provider, pre_state_pos, claim)?;
-                let proof = Self::fetch_proof_at(&self.provider, pre_state_pos, claim)?;
-                (pre_state, proof)
-            };
+            let (pre_state, proof) =
+                if matches!(claim.position.index_at_depth(), Ok(0)) && is_attack {
+                    let pre_state = self.provider.absolute_prestate();
+                    // TODO(clabby): There may be a proof for the absolute prestate in Cannon.
+                    let proof: Arc<[u8]> = Arc::new([]);
+
+                    (pre_state, proof)
+                } else {
+                    // If the move is an attack, the pre-state is left of the attacked claim's
+                    // position. If the move is a defense, the pre-state for the step is at the
+                    // claim's position.
+                    //
+                    // SAFETY: We can subtract 1 here due to the above check - we will never
+                    // underflow the level.
+                    let pre_state_pos = claim.position - is_attack as u128;
+
+                    let pre_state = Self::fetch_state_at(&self.provider, pre_state_pos)?;
+                    let proof = Self::fetch_proof_at(&self.provider, pre_state_pos)?;
+                    (pre_state, proof)
+                };
 
             Ok(FaultSolverResponse::Step(
                 is_attack,
@@ -114,9 +112,13 @@ where
                 proof,
             ))
         } else {
+            // Determine if the response will be an attack or a defense. The VM status byte is
+            // only meaningful for leaf claims, so it is ignored when comparing claims above the
+            // max depth.
+            let is_attack = !claims_equal_ignoring_status(&self_state_hash, &claim.value);
+
             // Fetch the local trace provider's opinion of the state hash at the move's position.
-            let claim_hash =
-                Self::fetch_state_hash(&self.provider, claim.position.make_move(is_attack), claim)?;
+            let claim_hash = self.fetch_state_hash(claim.position.make_move(is_attack))?;
 
             // If the local opinion of the state hash at the claim's position is different than
             // the claim's opinion about the state, then the proper move is to attack the claim.
@@ -133,6 +135,13 @@ where
     fn provider(&self) -> &P {
         &self.provider
     }
+
+    fn state_hash(&self, position: Position) -> anyhow::Result<Claim> {
+        match self.overrides.get(&position) {
+            Some(claim) => Ok(*claim),
+            None => self.provider.state_hash(position),
+        }
+    }
 }
 
 impl<T, P> AlphaClaimSolver<T, P>
@@ -140,52 +149,45 @@ where
     T: AsRef<[u8]>,
     P: TraceProvider<T>,
 {
-    fn new(provider: P) -> Self {
+    pub fn new(provider: P) -> Self {
         Self {
             provider,
+            overrides: HashMap::new(),
             _phantom: PhantomData,
         }
     }
 
-    /// Fetches the state hash at a given position from a [TraceProvider].
-    /// If the fetch fails, the claim is marked as unvisited and the error is returned.
+    /// Sets the local opinions that take precedence over the state hashes served by the
+    /// provider at their positions. This allows the honest value at a position to be patched
+    /// when it is known out-of-band, e.g. to work around a provider bug.
+    pub fn with_overrides(mut self, overrides: HashMap<Position, Claim>) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// Fetches the local opinion of the state hash at a given position, preferring an override
+    /// over the [TraceProvider]. Errors are wrapped in a [ProviderError].
     #[inline]
-    pub(crate) fn fetch_state_hash(
-        provider: &P,
-        position: Position,
-        observed_claim: &mut ClaimData,
-    ) -> anyhow::Result<Claim> {
-        let state_hash = provider.state_hash(position).map_err(|e| {
-            observed_claim.visited = false;
-            e
-        })?;
-        Ok(state_hash)
+    pub(crate) fn fetch_state_hash(&self, position: Position) -> anyhow::Result<Claim> {
+        let _span = tracing::debug_span!("provider.state_hash", position = %position).entered();
+        self.state_hash(position)
+            .map_err(|e| ProviderError(e).into())
     }
 
     #[inline]
-    pub(crate) fn fetch_state_at(
-        provider: &P,
-        position: Position,
-        observed_claim: &mut ClaimData,
-    ) -> anyhow::Result<Arc<T>> {
-        let state_at = provider.state_at(position).map_err(|e| {
-            observed_claim.visited = false;
-            e
-        })?;
-        Ok(state_at)
+    pub(crate) fn fetch_state_at(provider: &P, position: Position) -> anyhow::Result<Arc<T>> {
+        let _span = tracing::debug_span!("provider.state_at", position = %position).entered();
+        provider
+            .state_at(position)
+            .map_err(|e| ProviderError(e).into())
     }
 
     #[inline]
-    pub(crate) fn fetch_proof_at(
-        provider: &P,
-        position: Position,
-        observed_claim: &mut ClaimData,
-    ) -> anyhow::Result<Arc<[u8]>> {
-        let proof_at = provider.proof_at(position).map_err(|e| {
-            observed_claim.visited = false;
-            e
-        })?;
-        Ok(proof_at)
+    pub(crate) fn fetch_proof_at(provider: &P, position: Position) -> anyhow::Result<Arc<[u8]>> {
+        let _span = tracing::debug_span!("provider.proof_at", position = %position).entered();
+        provider
+            .proof_at(position)
+            .map_err(|e| ProviderError(e).into())
     }
 }
 
@@ -213,18 +215,17 @@ pub mod rules {
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::{providers::AlphabetTraceProvider, ClaimData, FaultDisputeSolver};
-    use alloy_primitives::hex;
+    use crate::{providers::AlphabetTraceProvider, ClaimData, FaultDisputeSolver, VMStatus};
+    use alloy_primitives::{hex, Address};
     use durin_primitives::{Claim, DisputeSolver, GameStatus};
 
-    fn mocks() -> (
-        FaultDisputeSolver<
-            [u8; 1],
-            AlphabetTraceProvider,
-            AlphaClaimSolver<[u8; 1], AlphabetTraceProvider>,
-        >,
-        Claim,
-    ) {
+    type AlphabetSolver = FaultDisputeSolver<
+        [u8; 1],
+        AlphabetTraceProvider,
+        AlphaClaimSolver<[u8; 1], AlphabetTraceProvider>,
+    >;
+
+    fn mocks() -> (AlphabetSolver, Claim) {
         let provider = AlphabetTraceProvider::new(b'a', 4);
         let claim_solver = AlphaClaimSolver::new(provider);
         let solver = FaultDisputeSolver::new(claim_solver);
@@ -239,12 +240,16 @@ mod test {
         let (solver, root_claim) = mocks();
         let moves = [
             (
-                solver.provider().state_hash(1).unwrap(),
+                solver.provider().state_hash(Position::from(1)).unwrap(),
                 FaultSolverResponse::Skip(0),
             ),
             (
                 root_claim,
-                FaultSolverResponse::Move(true, 0, solver.provider().state_hash(2).unwrap()),
+                FaultSolverResponse::Move(
+                    true,
+                    0,
+                    solver.provider().state_hash(Position::from(2)).unwrap(),
+                ),
             ),
         ];
 
@@ -254,8 +259,10 @@ mod test {
                     parent_index: u32::MAX,
                     visited: false,
                     value: claim,
-                    position: 1,
+                    position: Position::from(1),
                     clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
                 }],
                 claim,
                 GameStatus::InProgress,
@@ -272,12 +279,20 @@ mod test {
         let (solver, root_claim) = mocks();
         let moves = [
             (
-                solver.provider().state_hash(4).unwrap(),
-                FaultSolverResponse::Move(false, 2, solver.provider().state_hash(10).unwrap()),
+                solver.provider().state_hash(Position::from(4)).unwrap(),
+                FaultSolverResponse::Move(
+                    false,
+                    2,
+                    solver.provider().state_hash(Position::from(10)).unwrap(),
+                ),
             ),
             (
                 root_claim,
-                FaultSolverResponse::Move(true, 2, solver.provider().state_hash(8).unwrap()),
+                FaultSolverResponse::Move(
+                    true,
+                    2,
+                    solver.provider().state_hash(Position::from(8)).unwrap(),
+                ),
             ),
         ];
 
@@ -288,22 +303,28 @@ mod test {
                         parent_index: u32::MAX,
                         visited: true,
                         value: root_claim,
-                        position: 1,
+                        position: Position::from(1),
                         clock: 0,
+                        claimant: Address::ZERO,
+                        countered_by: Address::ZERO,
                     },
                     ClaimData {
                         parent_index: 0,
                         visited: true,
-                        value: solver.provider().state_hash(2).unwrap(),
-                        position: 2,
+                        value: solver.provider().state_hash(Position::from(2)).unwrap(),
+                        position: Position::from(2),
                         clock: 0,
+                        claimant: Address::ZERO,
+                        countered_by: Address::ZERO,
                     },
                     ClaimData {
                         parent_index: 1,
                         visited: false,
                         value: claim,
-                        position: 4,
+                        position: Position::from(4),
                         clock: 0,
+                        claimant: Address::ZERO,
+                        countered_by: Address::ZERO,
                     },
                 ],
                 root_claim,
@@ -326,32 +347,40 @@ mod test {
                     parent_index: u32::MAX,
                     visited: false,
                     value: root_claim,
-                    position: 1,
+                    position: Position::from(1),
                     clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
                 },
                 // Right level; Wrong claim - SKIP
                 ClaimData {
                     parent_index: 0,
                     visited: false,
                     value: root_claim,
-                    position: 2,
+                    position: Position::from(2),
                     clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
                 },
                 // Wrong level; Right claim - DEFEND
                 ClaimData {
                     parent_index: 1,
                     visited: false,
-                    value: solver.provider().state_hash(4).unwrap(),
-                    position: 4,
+                    value: solver.provider().state_hash(Position::from(4)).unwrap(),
+                    position: Position::from(4),
                     clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
                 },
                 // Right level; Wrong claim - SKIP
                 ClaimData {
                     parent_index: 3,
                     visited: false,
                     value: root_claim,
-                    position: 8,
+                    position: Position::from(8),
                     clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
                 },
             ],
             root_claim,
@@ -362,15 +391,77 @@ mod test {
         let moves = solver.available_moves(&mut state).unwrap();
         assert_eq!(
             &[
-                FaultSolverResponse::Move(true, 0, solver.provider().state_hash(2).unwrap()),
+                FaultSolverResponse::Move(
+                    true,
+                    0,
+                    solver.provider().state_hash(Position::from(2)).unwrap()
+                ),
                 FaultSolverResponse::Skip(1),
-                FaultSolverResponse::Move(false, 2, solver.provider().state_hash(10).unwrap()),
+                FaultSolverResponse::Move(
+                    false,
+                    2,
+                    solver.provider().state_hash(Position::from(10)).unwrap()
+                ),
                 FaultSolverResponse::Skip(3)
             ],
             moves.as_ref()
         );
     }
 
+    #[test]
+    fn available_moves_ignore_status_mid_tree() {
+        let (solver, root_claim) = mocks();
+
+        // A claim that agrees with the honest trace except for its status byte.
+        let mut claim = solver.provider().state_hash(Position::from(4)).unwrap();
+        claim[0] = VMStatus::Valid as u8;
+
+        let mut state = FaultDisputeState::new(
+            vec![
+                ClaimData {
+                    parent_index: u32::MAX,
+                    visited: true,
+                    value: root_claim,
+                    position: Position::from(1),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 0,
+                    visited: true,
+                    value: solver.provider().state_hash(Position::from(2)).unwrap(),
+                    position: Position::from(2),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 1,
+                    visited: false,
+                    value: claim,
+                    position: Position::from(4),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        let moves = solver.available_moves(&mut state).unwrap();
+        assert_eq!(
+            &[FaultSolverResponse::Move(
+                false,
+                2,
+                solver.provider().state_hash(Position::from(10)).unwrap()
+            )],
+            moves.as_ref()
+        );
+    }
+
     #[test]
     fn available_moves_static_step() {
         let (solver, root_claim) = mocks();
@@ -393,32 +484,40 @@ mod test {
                         parent_index: u32::MAX,
                         visited: true,
                         value: root_claim,
-                        position: 1,
+                        position: Position::from(1),
                         clock: 0,
+                        claimant: Address::ZERO,
+                        countered_by: Address::ZERO,
                     },
                     // Honest Attack
                     ClaimData {
                         parent_index: 0,
                         visited: true,
-                        value: solver.provider().state_hash(2).unwrap(),
-                        position: 2,
+                        value: solver.provider().state_hash(Position::from(2)).unwrap(),
+                        position: Position::from(2),
                         clock: 0,
+                        claimant: Address::ZERO,
+                        countered_by: Address::ZERO,
                     },
                     // Wrong level; Wrong claim - ATTACK
                     ClaimData {
                         parent_index: 1,
                         visited: true,
                         value: root_claim,
-                        position: 4,
+                        position: Position::from(4),
                         clock: 0,
+                        claimant: Address::ZERO,
+                        countered_by: Address::ZERO,
                     },
                     // Honest Attack
                     ClaimData {
                         parent_index: 2,
                         visited: true,
-                        value: solver.provider().state_hash(8).unwrap(),
-                        position: 8,
+                        value: solver.provider().state_hash(Position::from(8)).unwrap(),
+                        position: Position::from(8),
                         clock: 0,
+                        claimant: Address::ZERO,
+                        countered_by: Address::ZERO,
                     },
                     // Wrong level; Wrong claim - ATTACK STEP
                     ClaimData {
@@ -427,10 +526,12 @@ mod test {
                         value: if wrong_leaf {
                             root_claim
                         } else {
-                            solver.provider().state_hash(16).unwrap()
+                            solver.provider().state_hash(Position::from(16)).unwrap()
                         },
-                        position: 16,
+                        position: Position::from(16),
                         clock: 0,
+                        claimant: Address::ZERO,
+                        countered_by: Address::ZERO,
                     },
                 ],
                 root_claim,
@@ -3,3 +3,6 @@
 
 mod alpha;
 pub use self::alpha::*;
+
+mod proposer;
+pub use self::proposer::ProposerSolver;
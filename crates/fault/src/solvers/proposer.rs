@@ -0,0 +1,394 @@
+//! Implementation of the [FaultClaimSolver] trait for a proposer defending its own root claim.
+
+use crate::{
+    claims_equal_ignoring_status, AlphaClaimSolver, FaultClaimSolver, FaultDisputeGame,
+    FaultDisputeState, FaultSolverResponse, Gindex, Position, TraceProvider,
+};
+use alloy_primitives::Address;
+use durin_primitives::{Claim, DisputeGame};
+use std::collections::HashMap;
+
+/// The proposer solver plays only defensive moves on behalf of the proposer of the root
+/// claim. It counters challenger claims in support of the root claim and never counters
+/// claims on its own side of the game. If the local opinion of the root claim disagrees
+/// with the game's root claim, the root is not the proposer's to defend and every claim
+/// is skipped.
+pub struct ProposerSolver<T, P>
+where
+    T: AsRef<[u8]>,
+    P: TraceProvider<T>,
+{
+    inner: AlphaClaimSolver<T, P>,
+}
+
+impl<T, P> FaultClaimSolver<T, P> for ProposerSolver<T, P>
+where
+    T: AsRef<[u8]>,
+    P: TraceProvider<T>,
+{
+    fn solve_claim(
+        &self,
+        world: &FaultDisputeState,
+        claim_index: usize,
+        attacking_root: bool,
+    ) -> anyhow::Result<FaultSolverResponse<T>> {
+        // The proposer never attacks the root claim. If the local opinion disagrees with the
+        // root claim, there is nothing to defend.
+        if attacking_root {
+            world
+                .state()
+                .get(claim_index)
+                .ok_or(anyhow::anyhow!("Failed to fetch claim from passed state"))?;
+            return Ok(FaultSolverResponse::Skip(claim_index));
+        }
+
+        // When defending the root, the proposer's responses are the same as those of an honest
+        // solver that agrees with the root claim.
+        self.inner.solve_claim(world, claim_index, false)
+    }
+
+    fn provider(&self) -> &P {
+        self.inner.provider()
+    }
+
+    fn state_hash(&self, position: Position) -> anyhow::Result<Claim> {
+        self.inner.state_hash(position)
+    }
+}
+
+impl<T, P> ProposerSolver<T, P>
+where
+    T: AsRef<[u8]>,
+    P: TraceProvider<T>,
+{
+    pub fn new(provider: P) -> Self {
+        Self {
+            inner: AlphaClaimSolver::new(provider),
+        }
+    }
+
+    /// Sets the local opinions that take precedence over the state hashes served by the
+    /// provider at their positions, as in [AlphaClaimSolver::with_overrides].
+    pub fn with_overrides(mut self, overrides: HashMap<Position, Claim>) -> Self {
+        self.inner = self.inner.with_overrides(overrides);
+        self
+    }
+
+    /// Computes every move that the proposer must make to keep the root claim uncountered
+    /// against the current attacks, rather than only the responses to unvisited claims. Each
+    /// claim countering one of the proposer's claims must itself be countered. An existing
+    /// counter that agrees with the local opinion is reused, and the claims countering it are
+    /// defended in turn. Claims that were already countered by a step need no defense.
+    ///
+    /// ### Takes
+    /// - `world`: The [FaultDisputeState] to defend.
+    /// - `now`: The current timestamp, in seconds. Moves that would not be accepted by the
+    ///   contract at this time are not returned.
+    ///
+    /// ### Returns
+    /// - The defensive moves and steps, ordered by the index of the claim they counter, or an
+    ///   error if the state DAG is malformed or the provider fails. If the local opinion
+    ///   disagrees with the root claim, there is nothing to defend.
+    pub fn required_defenses(
+        &self,
+        world: &FaultDisputeState,
+        now: u64,
+    ) -> anyhow::Result<Vec<FaultSolverResponse<T>>> {
+        let Some(root) = world.topological_order()?.first().copied() else {
+            return Ok(Vec::new());
+        };
+        if self.state_hash(world.state()[root].position)? != world.root_claim() {
+            return Ok(Vec::new());
+        }
+
+        let children = world.children_indices()?;
+        let is_honest = |index: usize| -> anyhow::Result<bool> {
+            let claim = &world.state()[index];
+            let local = self.state_hash(claim.position)?;
+            Ok(if claim.position.depth() == world.max_depth {
+                world.needs_step(index, local) != Some(true)
+            } else {
+                claims_equal_ignoring_status(&local, &claim.value)
+            })
+        };
+
+        // Walk down from the root through the proposer's claims, each of which must be left
+        // uncountered.
+        let mut defenses = Vec::new();
+        let mut defended = vec![root];
+        while let Some(index) = defended.pop() {
+            for attack in children[index]
+                .iter()
+                .filter(|c| world.is_structural_counter(**c))
+            {
+                if world.state()[*attack].countered_by != Address::ZERO {
+                    continue;
+                }
+
+                let mut honest_counters = Vec::new();
+                for c in &children[*attack] {
+                    if world.is_structural_counter(*c) && is_honest(*c)? {
+                        honest_counters.push(*c);
+                    }
+                }
+                if !honest_counters.is_empty() {
+                    defended.extend(honest_counters);
+                    continue;
+                }
+
+                match self.inner.solve_claim(world, *attack, false)? {
+                    FaultSolverResponse::Move(is_attack, ..)
+                        if world.is_move_legal(*attack, is_attack, now).is_err() => {}
+                    response @ (FaultSolverResponse::Move(..) | FaultSolverResponse::Step(..)) => {
+                        defenses.push(response)
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        defenses.sort_by_key(|r| r.claim_index());
+        Ok(defenses)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        providers::AlphabetTraceProvider, state::alphabet_claim, ClaimData, FaultDisputeSolver,
+        Position,
+    };
+    use alloy_primitives::{hex, Address};
+    use durin_primitives::{Claim, DisputeSolver, GameStatus};
+
+    type AlphabetProposer = FaultDisputeSolver<
+        [u8; 1],
+        AlphabetTraceProvider,
+        ProposerSolver<[u8; 1], AlphabetTraceProvider>,
+    >;
+
+    fn mocks() -> (AlphabetProposer, Claim) {
+        let provider = AlphabetTraceProvider::new(b'a', 4);
+        let solver = FaultDisputeSolver::new(ProposerSolver::new(provider));
+        let bad_claim = Claim::from_slice(&hex!(
+            "c0ffee00c0de0000000000000000000000000000000000000000000000000000"
+        ));
+        (solver, bad_claim)
+    }
+
+    #[test]
+    fn proposer_defends_root() {
+        let (solver, bad_claim) = mocks();
+        let root_claim = solver.provider().state_hash(Position::from(1)).unwrap();
+        let cases = [
+            // The challenger's attack disagrees with the honest trace - attack it.
+            (
+                bad_claim,
+                FaultSolverResponse::Move(
+                    true,
+                    1,
+                    solver.provider().state_hash(Position::from(4)).unwrap(),
+                ),
+            ),
+            // The challenger's attack agrees with the honest trace - defend against it.
+            (
+                solver.provider().state_hash(Position::from(2)).unwrap(),
+                FaultSolverResponse::Move(
+                    false,
+                    1,
+                    solver.provider().state_hash(Position::from(6)).unwrap(),
+                ),
+            ),
+        ];
+
+        for (claim, expected_move) in cases {
+            let mut state = FaultDisputeState::new(
+                vec![
+                    ClaimData {
+                        parent_index: u32::MAX,
+                        visited: false,
+                        value: root_claim,
+                        position: Position::from(1),
+                        clock: 0,
+                        claimant: Address::ZERO,
+                        countered_by: Address::ZERO,
+                    },
+                    ClaimData {
+                        parent_index: 0,
+                        visited: false,
+                        value: claim,
+                        position: Position::from(2),
+                        clock: 0,
+                        claimant: Address::ZERO,
+                        countered_by: Address::ZERO,
+                    },
+                ],
+                root_claim,
+                GameStatus::InProgress,
+                4,
+            );
+
+            let moves = solver.available_moves(&mut state).unwrap();
+            assert_eq!(
+                &[FaultSolverResponse::Skip(0), expected_move],
+                moves.as_ref()
+            );
+        }
+    }
+
+    #[test]
+    fn proposer_defends_deep_bisection() {
+        let (solver, bad_claim) = mocks();
+        let root_claim = solver.provider().state_hash(Position::from(1)).unwrap();
+        let mut state = FaultDisputeState::new(
+            vec![
+                ClaimData {
+                    parent_index: u32::MAX,
+                    visited: true,
+                    value: root_claim,
+                    position: Position::from(1),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                // Challenger attack
+                ClaimData {
+                    parent_index: 0,
+                    visited: true,
+                    value: bad_claim,
+                    position: Position::from(2),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                // Proposer attack
+                ClaimData {
+                    parent_index: 1,
+                    visited: true,
+                    value: solver.provider().state_hash(Position::from(4)).unwrap(),
+                    position: Position::from(4),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                // Challenger defense - SKIP own claim, counter the challenger.
+                ClaimData {
+                    parent_index: 2,
+                    visited: false,
+                    value: bad_claim,
+                    position: Position::from(10),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        let moves = solver.available_moves(&mut state).unwrap();
+        assert_eq!(
+            &[FaultSolverResponse::Move(
+                true,
+                3,
+                solver.provider().state_hash(Position::from(20)).unwrap()
+            )],
+            moves.as_ref()
+        );
+    }
+
+    #[test]
+    fn proposer_skips_dishonest_root() {
+        let (solver, bad_claim) = mocks();
+        let mut state = FaultDisputeState::new(
+            vec![
+                ClaimData {
+                    parent_index: u32::MAX,
+                    visited: false,
+                    value: bad_claim,
+                    position: Position::from(1),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 0,
+                    visited: false,
+                    value: bad_claim,
+                    position: Position::from(2),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+            ],
+            bad_claim,
+            GameStatus::InProgress,
+            4,
+        );
+
+        let moves = solver.available_moves(&mut state).unwrap();
+        assert_eq!(
+            &[FaultSolverResponse::Skip(0), FaultSolverResponse::Skip(1)],
+            moves.as_ref()
+        );
+        assert!(state.state().iter().all(|c| c.visited));
+    }
+
+    #[test]
+    fn required_defenses_branching_attack() {
+        let solver = ProposerSolver::new(AlphabetTraceProvider::new(b'a', 4));
+        let honest = |position| solver.provider().state_hash(position).unwrap();
+        let bad_claim = Claim::repeat_byte(0xFF);
+        let claim = |parent_index, position, value| ClaimData {
+            visited: true,
+            ..alphabet_claim(parent_index, position, value)
+        };
+        let state = FaultDisputeState::new(
+            vec![
+                claim(u32::MAX, Position::from(1), honest(Position::from(1))),
+                // Challenger attack, countered by the proposer.
+                claim(0, Position::from(2), bad_claim),
+                claim(1, Position::from(4), honest(Position::from(4))),
+                // The challenger both attacks and defends the proposer's counter.
+                claim(2, Position::from(8), bad_claim),
+                claim(2, Position::from(10), bad_claim),
+                // A defense of the root claim does not counter it.
+                claim(0, Position::from(3), bad_claim),
+            ],
+            honest(Position::from(1)),
+            GameStatus::InProgress,
+            4,
+        );
+
+        assert_eq!(
+            solver.required_defenses(&state, 0).unwrap(),
+            vec![
+                FaultSolverResponse::Move(true, 3, honest(Position::from(16))),
+                FaultSolverResponse::Move(true, 4, honest(Position::from(20))),
+            ]
+        );
+
+        // Without the proposer's counter, only the challenger's attack on the root needs a
+        // defense.
+        let mut unanswered = state.clone();
+        unanswered.state_mut().truncate(2);
+        assert_eq!(
+            solver.required_defenses(&unanswered, 0).unwrap(),
+            vec![FaultSolverResponse::Move(
+                true,
+                1,
+                honest(Position::from(4))
+            )]
+        );
+
+        // Once the clocks have expired, no more defenses can be made.
+        let now = state.game_duration;
+        assert!(solver.required_defenses(&state, now).unwrap().is_empty());
+        assert!(solver
+            .required_defenses(&unanswered, now)
+            .unwrap()
+            .is_empty());
+    }
+}
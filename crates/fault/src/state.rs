@@ -2,18 +2,147 @@
 
 #![allow(dead_code, unused_variables)]
 
-use crate::{Clock, FaultDisputeGame, Position};
+use crate::{
+    claims_equal_ignoring_status,
+    dispatch::{resolve_calldata, resolve_claim_calldata},
+    rules::ResolutionRule,
+    BisectionConvention, ChessClock, ClaimDag, Clock, FaultClaimSolver, FaultDisputeGame,
+    FaultSolverResponse, Gindex, Position, TraceProvider, VMStatus,
+};
+use alloy_primitives::Address;
 use durin_primitives::{Claim, DisputeGame, GameStatus};
+use serde::{Deserialize, Serialize};
+use std::{collections::HashSet, fmt::Write, mem, ops::RangeInclusive, sync::Arc};
+
+/// The default duration of a [crate::FaultDisputeGame] in seconds. Each side of the
+/// chess clock is allotted half of this duration.
+pub const DEFAULT_GAME_DURATION: u64 = 7 * 24 * 60 * 60;
 
 /// The [ClaimData] struct holds the data associated with a claim within a
 /// [crate::FaultDisputeGame]'s state on-chain.
-#[derive(Debug, Clone, Copy)]
+///
+/// In its serialized form, the [Claim] and [Address] fields are hex strings and the
+/// `position` and `clock` are decimal strings, so that they survive the 53-bit integer limit
+/// of JSON numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ClaimData {
     pub parent_index: u32,
+    #[serde(default)]
     pub visited: bool,
     pub value: Claim,
+    #[serde(with = "decimal")]
     pub position: Position,
+    #[serde(with = "decimal")]
     pub clock: Clock,
+    /// The address of the account that made the claim.
+    pub claimant: Address,
+    /// The address of the claimant of the claim that countered this claim's subgame, or
+    /// [Address::ZERO] if the subgame is unresolved or resolved uncountered.
+    pub countered_by: Address,
+}
+
+/// Creates an unvisited, uncountered [ClaimData] with a zero clock and claimant, for building
+/// state DAGs in tests.
+#[cfg(test)]
+pub(crate) fn alphabet_claim(parent_index: u32, position: Position, value: Claim) -> ClaimData {
+    ClaimData {
+        parent_index,
+        visited: false,
+        value,
+        position,
+        clock: 0,
+        claimant: Address::ZERO,
+        countered_by: Address::ZERO,
+    }
+}
+
+/// Serializes integers as decimal strings.
+mod decimal {
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use std::{fmt::Display, str::FromStr};
+
+    pub(crate) fn serialize<S, T>(value: &T, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+        T: Display,
+    {
+        serializer.collect_str(value)
+    }
+
+    pub(crate) fn deserialize<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+    where
+        D: Deserializer<'de>,
+        T: FromStr,
+        T::Err: Display,
+    {
+        let value = String::deserialize(deserializer)?;
+        value.parse::<T>().map_err(D::Error::custom)
+    }
+}
+
+/// The [StateDiff] struct describes the claims that changed between two snapshots of a
+/// [FaultDisputeState]. Claims are only ever appended to the state DAG, so a claim either
+/// appears for the first time or gains a new child that counters it.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StateDiff {
+    /// The indices of the claims that were added to the state DAG.
+    pub added: Vec<usize>,
+    /// The indices of previously existing claims that were countered by an added claim.
+    pub countered: Vec<usize>,
+}
+
+/// The [DivergenceSummary] struct summarizes how the contested claims of a
+/// [FaultDisputeState] compare against the honest trace, as computed by
+/// [FaultDisputeState::divergence_summary].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DivergenceSummary {
+    /// The number of contested claims that agree with the honest trace.
+    pub agreeing: usize,
+    /// The number of contested claims that disagree with the honest trace.
+    pub disagreeing: usize,
+    /// The depth of the shallowest contested claim that disagrees with the honest trace, or
+    /// [None] if every contested claim agrees with it.
+    pub shallowest_disagreement: Option<u8>,
+}
+
+/// The position, raw data, and proof of the pre-state of a step.
+type StepPrestate<T> = (Option<Position>, Arc<T>, Arc<[u8]>);
+
+/// The [StepBundle] struct holds everything needed to build a step transaction against a
+/// leaf claim, as computed by [FaultDisputeState::step_bundle].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StepBundle<T: AsRef<[u8]>> {
+    /// The position of the pre-state, or [None] if the pre-state is the absolute prestate.
+    pub prestate_pos: Option<Position>,
+    /// The raw pre-state of the disputed transition.
+    pub prestate_data: Arc<T>,
+    /// The proof for the pre-state.
+    pub prestate_proof: Arc<[u8]>,
+    /// The claim in the state DAG that commits to the post-state of the disputed transition.
+    pub post_claim: Claim,
+    /// The position of the post-state claim.
+    pub post_claim_pos: Position,
+}
+
+/// The [TreeView] struct is a serializable, nested view of a claim and its subtree in the
+/// position tree of a [FaultDisputeState], as computed by [FaultDisputeState::to_tree_view].
+/// It is decoupled from the layout of [ClaimData], and is stable for external consumers.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TreeView {
+    /// The position of the claim.
+    #[serde(with = "decimal")]
+    pub position: Position,
+    /// The depth of the claim's position.
+    pub depth: u8,
+    /// The value of the claim.
+    pub value: Claim,
+    /// The address of the account that made the claim.
+    pub claimant: Address,
+    /// Whether or not the claim is currently countered, either by a step or by an uncountered
+    /// child that effectively counters it.
+    pub countered: bool,
+    /// The views of the claims that responded to the claim, ordered by claim index.
+    pub children: Vec<TreeView>,
 }
 
 /// the [FaultDisputeState] struct holds the in-memory representation of a
@@ -33,6 +162,16 @@ pub struct FaultDisputeState {
     status: GameStatus,
     /// The max depth of the position tree.
     pub max_depth: u8,
+    /// The duration of the game in seconds. Each side of the chess clock is
+    /// allotted half of this duration.
+    pub game_duration: u64,
+    /// The timestamp at which the freeze period starts. Chess clocks are paused for the
+    /// duration of the freeze period.
+    pub freeze_start: u64,
+    /// The duration of the freeze period in seconds.
+    pub freeze_duration: u64,
+    /// The indices of the claims whose subgames have been resolved.
+    resolved_subgames: HashSet<usize>,
 }
 
 impl FaultDisputeState {
@@ -47,7 +186,1294 @@ impl FaultDisputeState {
             root_claim,
             status,
             max_depth,
+            game_duration: DEFAULT_GAME_DURATION,
+            freeze_start: 0,
+            freeze_duration: 0,
+            resolved_subgames: HashSet::new(),
+        }
+    }
+
+    /// Resolves the game, accounting for the validity of the anchor state that the game
+    /// starts from. If the anchor state is invalid, the game is invalidated and the
+    /// challenger wins regardless of the state DAG.
+    ///
+    /// ### Takes
+    /// - `anchor_valid`: Whether or not the game's anchor state is valid.
+    /// - `sim`: If true, the resulting [GameStatus] is returned without being written back
+    ///   to the state.
+    ///
+    /// ### Returns
+    /// - [GameStatus] or [Err]: The status of the game after resolution, or an error if the
+    ///   state DAG is malformed.
+    pub fn resolve_with_anchor(
+        &mut self,
+        anchor_valid: bool,
+        sim: bool,
+    ) -> anyhow::Result<GameStatus> {
+        let status = if anchor_valid {
+            self.resolved_status()?
+        } else {
+            GameStatus::ChallengerWins
+        };
+
+        if !sim {
+            self.status = status.clone();
+        }
+        Ok(status)
+    }
+
+    /// Resolves the game, checking a set of invariants of the state before and after
+    /// resolution. See [crate::rules] for the built-in resolution rules.
+    ///
+    /// ### Takes
+    /// - `pre`: The [ResolutionRule]s to check against the state before resolution.
+    /// - `post`: The [ResolutionRule]s to check against the resolved state.
+    ///
+    /// ### Returns
+    /// - [GameStatus] or [Err]: The status of the game after resolution, or the error of the
+    ///   first violated rule. The status is only written back if every rule holds.
+    pub fn resolve_with_rules(
+        &mut self,
+        pre: &[ResolutionRule],
+        post: &[ResolutionRule],
+    ) -> anyhow::Result<GameStatus> {
+        pre.iter().try_fold(&*self, |state, rule| rule(state))?;
+
+        // The post rules are checked against the state with its resolved status in place, and
+        // the previous status is restored if any of them is violated.
+        let status = self.resolved_status()?;
+        let previous = mem::replace(&mut self.status, status);
+        if let Err(e) = post.iter().try_fold(&*self, |state, rule| rule(state)) {
+            self.status = previous;
+            return Err(e);
+        }
+        Ok(self.status.clone())
+    }
+
+    /// Checks that the first claim of the state DAG is the root claim, as it is in the contract.
+    /// The solver treats the claim at index 0 as the root, so a state loaded with any other
+    /// claim first would be solved incorrectly.
+    ///
+    /// ### Returns
+    /// - `()` or [Err]: An error if the first claim is not at the root position, or if it has
+    ///   a parent.
+    pub fn assert_root_invariants(&self) -> anyhow::Result<()> {
+        let Some(root) = self.state.first() else {
+            return Ok(());
+        };
+        if !root.position.is_valid() {
+            anyhow::bail!("Root claim has invalid position {}", root.position);
+        }
+        if root.position != Position::ROOT {
+            anyhow::bail!("Root claim is at position {}, expected 1", root.position);
+        }
+        if root.parent_index != u32::MAX {
+            anyhow::bail!("Root claim has parent index {}", root.parent_index);
+        }
+        Ok(())
+    }
+
+    /// Validates the structure of the state DAG, checking the root claim invariants, that the
+    /// claims form a single tree of valid positions, and that no claim was countered by its own
+    /// claimant. With strict validation, claims between the root and the max depth must also
+    /// carry the [VMStatus::Unfinished] status byte. These claims only bisect the trace, so a
+    /// set status byte marks a corrupt or adversarial claim. Strict validation is only
+    /// meaningful for backends that stamp intermediate states as unfinished.
+    ///
+    /// ### Takes
+    /// - `strict`: Whether or not to check the status bytes of mid-tree claims.
+    ///
+    /// ### Returns
+    /// - `()` or [Err]: An error describing the first malformed claim found.
+    pub fn validate(&self, strict: bool) -> anyhow::Result<()> {
+        self.assert_root_invariants()?;
+        self.topological_order()?;
+
+        if let Some(i) = self.state.iter().position(|c| !c.position.is_valid()) {
+            anyhow::bail!(
+                "Claim {} has invalid position {}",
+                i,
+                self.state[i].position
+            );
+        }
+
+        let self_countered = self
+            .state
+            .iter()
+            .position(|c| c.countered_by != Address::ZERO && c.countered_by == c.claimant);
+        if let Some(i) = self_countered {
+            anyhow::bail!(
+                "Claim {} was countered by its own claimant {}",
+                i,
+                self.state[i].claimant
+            );
+        }
+
+        if strict {
+            let suspicious = self.state.iter().enumerate().find(|(_, c)| {
+                let depth = c.position.depth();
+                depth > 0 && depth < self.max_depth && c.value[0] != VMStatus::Unfinished as u8
+            });
+            if let Some((i, claim)) = suspicious {
+                anyhow::bail!(
+                    "Claim {} at depth {} carries VM status {}",
+                    i,
+                    claim.position.depth(),
+                    claim.value[0]
+                );
+            }
+        }
+        Ok(())
+    }
+
+    /// Orders the claims of the state DAG such that every claim comes after its parent. Claims
+    /// are usually appended after their parents, but a loaded state is not guaranteed to be
+    /// topologically sorted, so parents may appear at any index.
+    ///
+    /// ### Returns
+    /// - `Vec<usize>` or [Err]: The claim indices in topological order, starting with the
+    ///   root claim, or an error if a parent is missing, the root claim is not unique, or the
+    ///   parent references form a cycle.
+    pub fn topological_order(&self) -> anyhow::Result<Vec<usize>> {
+        let children = self.children_indices()?;
+        let mut order = (0..self.state.len())
+            .filter(|i| self.state[*i].parent_index == u32::MAX)
+            .collect::<Vec<_>>();
+        if self.state.is_empty() {
+            return Ok(order);
+        }
+        if order.len() > 1 {
+            anyhow::bail!("State DAG has {} root claims", order.len());
+        }
+
+        // Every claim has a single parent, so a breadth-first walk from the root visits each
+        // claim that descends from it exactly once. Any claim left unvisited is part of a cycle.
+        let mut next = 0;
+        while next < order.len() {
+            order.extend_from_slice(&children[order[next]]);
+            next += 1;
+        }
+        if order.len() < self.state.len() {
+            let mut visited = vec![false; self.state.len()];
+            order.iter().for_each(|i| visited[*i] = true);
+            let i = visited.iter().position(|v| !v).unwrap_or_default();
+            anyhow::bail!("Claim {} is part of a cycle", i);
+        }
+        Ok(order)
+    }
+
+    /// Returns the indices of the children of each claim, regardless of the order in which the
+    /// claims appear in the state DAG.
+    pub(crate) fn children_indices(&self) -> anyhow::Result<Vec<Vec<usize>>> {
+        let mut children = vec![Vec::new(); self.state.len()];
+        for (i, claim) in self.state.iter().enumerate() {
+            if claim.parent_index == u32::MAX {
+                continue;
+            }
+            children
+                .get_mut(claim.parent_index as usize)
+                .ok_or(anyhow::anyhow!(
+                    "Claim {} references missing parent {}",
+                    i,
+                    claim.parent_index
+                ))?
+                .push(i);
+        }
+        Ok(children)
+    }
+
+    /// Computes the [GameStatus] of the game by resolving the state DAG as a tree of subgames,
+    /// bottom-up. A claim is countered if it has been countered by a step, or if any of the
+    /// claims that effectively counter it are left uncountered. If the root claim is countered,
+    /// the challenger wins; otherwise, the defender wins.
+    fn resolved_status(&self) -> anyhow::Result<GameStatus> {
+        let order = self.topological_order()?;
+        let countered = self.resolved_countered(&order);
+
+        Ok(match order.first() {
+            Some(root) if countered[*root] => GameStatus::ChallengerWins,
+            Some(_) => GameStatus::DefenderWins,
+            None => GameStatus::InProgress,
+        })
+    }
+
+    /// Computes whether each claim is countered once the state DAG is resolved as a tree of
+    /// subgames. A claim is countered if it has been countered by a step, or if any of the
+    /// claims that effectively counter it are left uncountered.
+    ///
+    /// ### Takes
+    /// - `order`: The claim indices in topological order, as returned by
+    ///   [FaultDisputeState::topological_order].
+    ///
+    /// ### Returns
+    /// - `Vec<bool>`: Whether each claim is countered, indexed by claim.
+    fn resolved_countered(&self, order: &[usize]) -> Vec<bool> {
+        // Visiting the claims in reverse topological order resolves every subgame before the
+        // subgame of its parent.
+        let mut countered = self
+            .state
+            .iter()
+            .map(|c| c.countered_by != Address::ZERO)
+            .collect::<Vec<_>>();
+        for i in order.iter().rev() {
+            if !countered[*i] && self.is_structural_counter(*i) {
+                countered[self.state[*i].parent_index as usize] = true;
+            }
+        }
+        countered
+    }
+
+    /// Checks if a move against the claim at `parent_index` would be accepted by the
+    /// `FaultDisputeGame` contract. This mirrors the checks performed in the contract's
+    /// `move` function.
+    ///
+    /// ### Takes
+    /// - `parent_index`: The index of the claim being countered within the state DAG.
+    /// - `is_attack`: Whether the move is an attack or a defense.
+    /// - `now`: The current timestamp, in seconds.
+    ///
+    /// ### Returns
+    /// - `Ok(())` if the move is legal, or an [Err] describing the first failed check.
+    pub fn is_move_legal(
+        &self,
+        parent_index: usize,
+        is_attack: bool,
+        now: u64,
+    ) -> anyhow::Result<()> {
+        let parent = self
+            .state
+            .get(parent_index)
+            .ok_or(anyhow::anyhow!("Parent claim does not exist"))?;
+
+        // The root claim may only be attacked.
+        if parent.parent_index == u32::MAX && !is_attack {
+            anyhow::bail!("Cannot defend the root claim");
+        }
+
+        // The resulting position must be within the bounds of the position tree.
+        let next_position = parent.position.make_move(is_attack);
+        if next_position.depth() > self.max_depth {
+            anyhow::bail!("Game depth exceeded");
+        }
+
+        // The same move against the parent may only be made once.
+        if self
+            .state
+            .iter()
+            .any(|c| c.parent_index as usize == parent_index && c.position == next_position)
+        {
+            anyhow::bail!("Claim already exists");
+        }
+
+        // The duration of the next clock may not exceed half of the game duration.
+        if self.challenge_duration(parent_index, now) > self.game_duration >> 1 {
+            anyhow::bail!("Clock time exceeded");
+        }
+
+        Ok(())
+    }
+
+    /// Checks that a response computed by a solver stays within the bounds of the position
+    /// tree. A move must not result in a position deeper than the max depth, and a step may
+    /// only be made against a claim at the max depth, as there is no instruction to step
+    /// beyond the last one. Responses that fail this check would be rejected by the contract.
+    ///
+    /// ### Takes
+    /// - `response`: The [FaultSolverResponse] to check.
+    ///
+    /// ### Returns
+    /// - `Ok(())` if the response is within bounds, or an [Err] describing the violation.
+    pub fn check_response_depth<T: AsRef<[u8]>>(
+        &self,
+        response: &FaultSolverResponse<T>,
+    ) -> anyhow::Result<()> {
+        let claim_index = response.claim_index();
+        let claim = self
+            .state
+            .get(claim_index)
+            .ok_or(anyhow::anyhow!("Claim does not exist"))?;
+
+        match response {
+            FaultSolverResponse::Move(is_attack, ..) => {
+                let position = claim.position.make_move(*is_attack);
+                if position.depth() > self.max_depth {
+                    anyhow::bail!(
+                        "Move against claim {} to position {} exceeds the max depth {}",
+                        claim_index,
+                        position,
+                        self.max_depth
+                    );
+                }
+            }
+            FaultSolverResponse::Step(..) if claim.position.depth() != self.max_depth => {
+                anyhow::bail!(
+                    "Step against claim {} at depth {} is not at the max depth {}",
+                    claim_index,
+                    claim.position.depth(),
+                    self.max_depth
+                );
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Determines whether a step is warranted against the leaf claim at the given index, and
+    /// in which direction. A leaf that disagrees with the honest leaf is attacked. A leaf that
+    /// agrees with it is defended, as the disagreement with its parent must then lie at the
+    /// next trace index. The exception is a correct leaf at the last trace index, which has no
+    /// next instruction to dispute and is skipped.
+    ///
+    /// ### Takes
+    /// - `claim_index`: The index of the claim within the state DAG.
+    /// - `honest_leaf`: The local opinion of the state hash at the claim's position. As the
+    ///   claim is a leaf, its VM status byte is compared as well.
+    ///
+    /// ### Returns
+    /// - `Some(is_attack)` if a step should be made against the claim, or [None] if the claim
+    ///   does not exist, is not at the max depth, or should be skipped.
+    pub fn needs_step(&self, claim_index: usize, honest_leaf: Claim) -> Option<bool> {
+        let claim = self.state.get(claim_index)?;
+        if claim.position.depth() != self.max_depth {
+            return None;
+        }
+
+        if claim.value != honest_leaf {
+            return Some(true);
+        }
+        // The rightmost leaf commits to the last trace index.
+        let last_leaf = Position::ROOT.right_index(self.max_depth);
+        (claim.position != last_leaf).then_some(false)
+    }
+
+    /// Returns the duration of the clock of a move against the claim at the given index, at
+    /// the given timestamp. This is the duration of the claim's parent clock plus the time
+    /// elapsed since the claim was made, excluding time spent within the freeze period.
+    fn challenge_duration(&self, index: usize, now: u64) -> u64 {
+        let claim = &self.state[index];
+        let parent_duration = self
+            .state
+            .get(claim.parent_index as usize)
+            .map(|c| c.clock.duration())
+            .unwrap_or_default();
+        let clock: Clock = ((parent_duration as u128) << 64) | claim.clock.timestamp() as u128;
+        clock.duration_at(now, self.freeze_start, self.freeze_duration)
+    }
+
+    /// Returns the timestamp at which the clock of a move against the claim at the given index
+    /// expires, after which the claim can no longer be countered.
+    ///
+    /// ### Returns
+    /// - The expiry timestamp, or [None] if the claim does not exist or its clock had already
+    ///   expired when it was made.
+    pub fn clock_expiry(&self, index: usize) -> Option<u64> {
+        // The clock expires once the duration of a move against the claim would exceed half of
+        // the game duration. At the time the claim was made, that duration is the time already
+        // spent on the clock.
+        let timestamp = self.state.get(index)?.clock.timestamp();
+        let spent = self.challenge_duration(index, timestamp);
+        let mut expiry = timestamp + (self.game_duration >> 1).checked_sub(spent)?;
+
+        // If the freeze period starts before the clock would expire, the clock is paused for
+        // the remainder of the freeze period.
+        if self.freeze_start < expiry {
+            expiry += (self.freeze_start + self.freeze_duration)
+                .saturating_sub(timestamp.max(self.freeze_start));
+        }
+        Some(expiry)
+    }
+
+    /// Returns the claims that the honest side must counter whose clocks expire within the
+    /// warning window, paired with the timestamp at which they expire. A claim must be
+    /// countered if it opposes the honest opinion of the root claim and has not yet been
+    /// countered by a move or a step. Claims whose clocks have already expired can no longer
+    /// be countered, and are not returned, nor are claims at invalid positions.
+    ///
+    /// ### Takes
+    /// - `now`: The current timestamp, in seconds.
+    /// - `warn_window`: The number of seconds ahead of `now` to warn about.
+    /// - `honest_root_opinion`: The honest opinion of the root claim.
+    ///
+    /// ### Returns
+    /// - The indices of the at-risk claims and their expiry timestamps, ordered by expiry.
+    pub fn at_risk_claims(
+        &self,
+        now: u64,
+        warn_window: u64,
+        honest_root_opinion: Claim,
+    ) -> Vec<(usize, u64)> {
+        let attacking_root = honest_root_opinion != self.root_claim;
+
+        let mut countered = vec![false; self.state.len()];
+        for i in 0..self.state.len() {
+            if self.is_structural_counter(i) {
+                countered[self.state[i].parent_index as usize] = true;
+            }
+        }
+
+        let mut at_risk = (0..self.state.len())
+            .filter(|i| {
+                let claim = &self.state[*i];
+                claim.position.is_valid()
+                    && claim.position.depth() % 2 != attacking_root as u8
+                    && !countered[*i]
+                    && claim.countered_by == Address::ZERO
+                    && (claim.parent_index == u32::MAX || self.is_structural_counter(*i))
+            })
+            .filter_map(|i| {
+                let expiry = self.clock_expiry(i)?;
+                (now <= expiry && expiry - now <= warn_window).then_some((i, expiry))
+            })
+            .collect::<Vec<_>>();
+        at_risk.sort_by_key(|(_, expiry)| *expiry);
+        at_risk
+    }
+
+    /// Resolves the subgame rooted at the claim at the given index, mirroring the contract's
+    /// `resolveClaim`. Subgames are resolved one at a time, bottom-up: the subgame is countered
+    /// if any of the claims that effectively counter it resolved uncountered, in which case
+    /// the claim's `countered_by` is set to that claim's claimant. As in the contract,
+    /// claimants are assumed to be non-zero.
+    ///
+    /// ### Takes
+    /// - `index`: The index of the claim at the root of the subgame.
+    /// - `now`: The current timestamp, in seconds.
+    ///
+    /// ### Returns
+    /// - `bool` or [Err]: Whether or not the subgame resolved countered, or an error if the
+    ///   subgame cannot be resolved yet.
+    pub fn resolve_subgame(&mut self, index: usize, now: u64) -> anyhow::Result<bool> {
+        if index >= self.state.len() {
+            anyhow::bail!("Claim does not exist");
+        }
+        if self.resolved_subgames.contains(&index) {
+            anyhow::bail!("Subgame already resolved");
+        }
+
+        // The subgame may only be resolved once no more moves can be made against its root.
+        if self.challenge_duration(index, now) < self.game_duration >> 1 {
+            anyhow::bail!("Clock not expired");
+        }
+
+        let children = (0..self.state.len())
+            .filter(|c| {
+                self.state[*c].parent_index as usize == index && self.is_structural_counter(*c)
+            })
+            .collect::<Vec<_>>();
+        if children.iter().any(|c| !self.resolved_subgames.contains(c)) {
+            anyhow::bail!("Subgame children are not resolved");
+        }
+
+        // A claim that has already been countered, e.g. by a step, remains countered.
+        if self.state[index].countered_by == Address::ZERO {
+            if let Some(child) = children
+                .iter()
+                .find(|c| self.state[**c].countered_by == Address::ZERO)
+            {
+                self.state[index].countered_by = self.state[*child].claimant;
+            }
+        }
+
+        self.resolved_subgames.insert(index);
+        Ok(self.state[index].countered_by != Address::ZERO)
+    }
+
+    /// Computes the outcome of every subgame that can be resolved at the given timestamp in a
+    /// single bottom-up pass, without resolving them. As in [FaultDisputeState::resolve_subgame],
+    /// a subgame resolves countered if its root claim has already been countered, or if any of
+    /// the claims that effectively counter it resolve uncountered. A subgame can only be
+    /// resolved once its clock has expired and all of its child subgames can be resolved.
+    ///
+    /// ### Takes
+    /// - `now`: The current timestamp, in seconds.
+    ///
+    /// ### Returns
+    /// - `Vec<(usize, bool)>` or [Err]: The index of each claim whose subgame can be resolved
+    ///   paired with whether or not it resolves countered, ordered by index, or an error if
+    ///   the state DAG is malformed.
+    pub fn subgame_outcomes(&self, now: u64) -> anyhow::Result<Vec<(usize, bool)>> {
+        let order = self.topological_order()?;
+        let mut countered = self
+            .state
+            .iter()
+            .map(|c| c.countered_by != Address::ZERO)
+            .collect::<Vec<_>>();
+        let mut resolvable = vec![true; self.state.len()];
+
+        // Visiting the claims in reverse topological order settles every subgame before the
+        // subgame of its parent.
+        for i in order.iter().rev() {
+            resolvable[*i] &= self.challenge_duration(*i, now) >= self.game_duration >> 1;
+            if self.is_structural_counter(*i) {
+                let parent = self.state[*i].parent_index as usize;
+                resolvable[parent] &= resolvable[*i];
+                countered[parent] |= !countered[*i];
+            }
+        }
+
+        Ok((0..self.state.len())
+            .filter(|i| resolvable[*i])
+            .map(|i| (i, countered[i]))
+            .collect())
+    }
+
+    /// Computes the outcome of every subgame that can be resolved next with
+    /// [FaultDisputeState::resolve_subgame] at the given timestamp, in a single pass and
+    /// without resolving them. Unlike [FaultDisputeState::subgame_outcomes], a subgame is only
+    /// returned once all of its child subgames have already been resolved, as the contract
+    /// requires.
+    ///
+    /// ### Takes
+    /// - `now`: The current timestamp, in seconds.
+    ///
+    /// ### Returns
+    /// - `Vec<(usize, bool)>`: The index of each claim whose subgame can be resolved next
+    ///   paired with whether or not it resolves countered, ordered by index.
+    pub fn resolvable_subgames(&self, now: u64) -> Vec<(usize, bool)> {
+        let mut children_resolved = vec![true; self.state.len()];
+        let mut first_uncountered = vec![None; self.state.len()];
+        for (i, claim) in self.state.iter().enumerate() {
+            if !self.is_structural_counter(i) {
+                continue;
+            }
+            let parent = claim.parent_index as usize;
+            children_resolved[parent] &= self.resolved_subgames.contains(&i);
+            if claim.countered_by == Address::ZERO {
+                first_uncountered[parent].get_or_insert(claim.claimant);
+            }
+        }
+
+        (0..self.state.len())
+            .filter(|i| {
+                !self.resolved_subgames.contains(i)
+                    && children_resolved[*i]
+                    && self.challenge_duration(*i, now) >= self.game_duration >> 1
+            })
+            .map(|i| {
+                let countered_by = match self.state[i].countered_by {
+                    Address::ZERO => first_uncountered[i].unwrap_or(Address::ZERO),
+                    countered_by => countered_by,
+                };
+                (i, countered_by != Address::ZERO)
+            })
+            .collect()
+    }
+
+    /// Finds the uncountered claim that, if countered, flips the outcome of the game to the
+    /// local opinion of the root claim. Only claims that can still be countered at the given
+    /// timestamp are considered: a move against the claim must be legal, or a step must be
+    /// possible before its clock expires if the claim is a leaf.
+    ///
+    /// ### Takes
+    /// - `now`: The current timestamp, in seconds.
+    /// - `honest_root_opinion`: The local opinion of the root claim.
+    ///
+    /// ### Returns
+    /// - The index of the first pivotal claim, or [None] if the game already resolves to the
+    ///   local opinion, no single counter flips it, or the state DAG is malformed.
+    pub fn pivotal_claim(&self, now: u64, honest_root_opinion: Claim) -> Option<usize> {
+        let order = self.topological_order().ok()?;
+        let root = *order.first()?;
+        let attacking_root = honest_root_opinion != self.root_claim;
+
+        // Resolves the state DAG as if the claim at `extra` had been countered, returning
+        // whether each claim ends up countered.
+        let countered_with = |extra: Option<usize>| {
+            let mut countered = self
+                .state
+                .iter()
+                .enumerate()
+                .map(|(i, c)| c.countered_by != Address::ZERO || Some(i) == extra)
+                .collect::<Vec<_>>();
+            for i in order.iter().rev() {
+                if !countered[*i] && self.is_structural_counter(*i) {
+                    countered[self.state[*i].parent_index as usize] = true;
+                }
+            }
+            countered
+        };
+
+        let countered = countered_with(None);
+        if countered[root] == attacking_root {
+            return None;
+        }
+
+        (0..self.state.len()).find(|i| {
+            let counterable = if self.state[*i].position.depth() == self.max_depth {
+                self.challenge_duration(*i, now) <= self.game_duration >> 1
+            } else {
+                self.is_move_legal(*i, true, now).is_ok()
+                    || self.is_move_legal(*i, false, now).is_ok()
+            };
+            !countered[*i]
+                && !self.is_subgame_resolved(*i)
+                && counterable
+                && countered_with(Some(*i))[root] == attacking_root
+        })
+    }
+
+    /// Encodes the calls that finalize the game: a `resolveClaim` call for each subgame that
+    /// has not been resolved yet, followed by a `resolve` call. Each subgame is resolved after
+    /// the subgames of all of its children, as required by the contract, so the calls may be
+    /// submitted sequentially or batched in order.
+    ///
+    /// ### Returns
+    /// - The calldata of each call in submission order, or an error if the state DAG is
+    ///   malformed.
+    pub fn resolve_calldata_batch(&self) -> anyhow::Result<Vec<Vec<u8>>> {
+        let mut batch = self
+            .topological_order()?
+            .into_iter()
+            .rev()
+            .filter(|i| !self.resolved_subgames.contains(i))
+            .map(resolve_claim_calldata)
+            .collect::<Vec<_>>();
+        batch.push(resolve_calldata());
+        Ok(batch)
+    }
+
+    /// Records that the subgame rooted at the claim at the given index has already been
+    /// resolved on-chain, e.g. when loading the state from the contract. Claims in resolved
+    /// subgames can no longer affect the outcome of the game, and are skipped by the solver.
+    pub fn mark_subgame_resolved(&mut self, index: usize) {
+        self.resolved_subgames.insert(index);
+    }
+
+    /// Returns whether or not the subgame rooted at the claim at the given index has been
+    /// resolved, either with [FaultDisputeState::resolve_subgame] or on-chain.
+    pub fn is_subgame_resolved(&self, index: usize) -> bool {
+        self.resolved_subgames.contains(&index)
+    }
+
+    /// Returns the index of the claim making the opposite move against the same parent as the
+    /// claim at the given index, i.e. the defense for an attack or the attack for a defense.
+    /// If both exist, both an attack and a defense have been made against the parent.
+    ///
+    /// ### Takes
+    /// - `index`: The index of the claim within the state DAG.
+    ///
+    /// ### Returns
+    /// - `Option<usize>`: The index of the sibling claim, or [None] if the claim does not
+    ///   exist, is not a move against its parent, or has no sibling.
+    pub fn sibling_claim(&self, index: usize) -> Option<usize> {
+        let claim = self.state.get(index)?;
+        let parent = self.state.get(claim.parent_index as usize)?;
+
+        let sibling_position = if claim.position == parent.position.make_move(true) {
+            parent.position.make_move(false)
+        } else if claim.position == parent.position.make_move(false) {
+            parent.position.make_move(true)
+        } else {
+            return None;
+        };
+        if sibling_position == claim.position {
+            return None;
+        }
+
+        self.state
+            .iter()
+            .position(|c| c.parent_index == claim.parent_index && c.position == sibling_position)
+    }
+
+    /// Returns the depth of the lowest common ancestor of the positions of two claims, i.e. the
+    /// depth at which their paths from the root diverge. The common ancestor is found from the
+    /// common prefix of the bit representations of the two positions.
+    ///
+    /// ### Takes
+    /// - `a`: The index of the first claim within the state DAG.
+    /// - `b`: The index of the second claim within the state DAG.
+    ///
+    /// ### Returns
+    /// - `Option<u8>`: The depth of the common ancestor, or [None] if either claim does not
+    ///   exist.
+    pub fn divergence_depth(&self, a: usize, b: usize) -> Option<u8> {
+        let a = self.state.get(a)?.position;
+        let b = self.state.get(b)?.position;
+
+        // Bring both positions up to the same depth, after which the bits that follow their
+        // common prefix are the moves below the common ancestor.
+        let depth = a.depth().min(b.depth());
+        let diverged =
+            Position((*a >> (a.depth() - depth) as usize) ^ (*b >> (b.depth() - depth) as usize));
+        if diverged == Position::default() {
+            return Some(depth);
+        }
+        Some(depth - diverged.depth() - 1)
+    }
+
+    /// Checks if the claim at the given index structurally counters its parent. See
+    /// [ClaimDag::is_structural_counter].
+    pub fn is_structural_counter(&self, index: usize) -> bool {
+        ClaimDag::is_structural_counter(self, index)
+    }
+
+    /// Marks every claim matching the predicate as visited, excluding it from the next solve
+    /// pass.
+    pub fn mark_visited_where(&mut self, pred: impl Fn(&ClaimData) -> bool) {
+        self.state
+            .iter_mut()
+            .filter(|c| pred(c))
+            .for_each(|c| c.visited = true);
+    }
+
+    /// Returns the indices of all claims within the state DAG whose position is at the
+    /// given depth. Claims at invalid positions are not at any depth, and are never returned.
+    pub fn claims_at_depth(&self, depth: u8) -> Vec<usize> {
+        self.state
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| {
+                (c.position.is_valid() && c.position.depth() == depth).then_some(i)
+            })
+            .collect()
+    }
+
+    /// Returns the indices of all claims within the state DAG made by the given claimant, for
+    /// accounting the bonds posted and gas spent by one side of the game.
+    pub fn honest_moves(&self, honest_addr: Address) -> Vec<usize> {
+        self.state
+            .iter()
+            .enumerate()
+            .filter_map(|(i, c)| (c.claimant == honest_addr).then_some(i))
+            .collect()
+    }
+
+    /// Returns the trace index disputed by the leaf claim at the given index.
+    ///
+    /// ### Takes
+    /// - `index`: The index of the claim within the state DAG.
+    ///
+    /// ### Returns
+    /// - `u64` or [Err]: The trace index that the claim commits to, or an error if the
+    ///   claim does not exist or is not at the max depth of the position tree.
+    pub fn disputed_trace_index(&self, index: usize) -> anyhow::Result<u64> {
+        let claim = self
+            .state
+            .get(index)
+            .ok_or(anyhow::anyhow!("Claim does not exist"))?;
+
+        let depth = claim.position.depth();
+        if depth != self.max_depth {
+            anyhow::bail!(
+                "Claim at depth {} is not a leaf; max depth is {}",
+                depth,
+                self.max_depth
+            );
         }
+
+        claim.position.trace_index(self.max_depth)
+    }
+
+    /// Returns the range of trace indices currently under dispute. A claim is contested if it is
+    /// effectively countered by another claim, and each disputed path narrows down to its
+    /// deepest contested claim, i.e. a contested claim with a counter that is not itself
+    /// contested. The window spans the trace ranges of these claims.
+    ///
+    /// ### Returns
+    /// - `Option<RangeInclusive<u64>>` or [Err]: The smallest range of trace indices covering
+    ///   every disputed path, or [None] if no claim is contested. Errors if a trace index does
+    ///   not fit within a [u64].
+    pub fn contested_window(&self) -> anyhow::Result<Option<RangeInclusive<u64>>> {
+        let mut contested = vec![false; self.state.len()];
+        for i in 0..self.state.len() {
+            if self.is_structural_counter(i) {
+                contested[self.state[i].parent_index as usize] = true;
+            }
+        }
+
+        // A contested claim is the deepest on a path if one of its counters is not contested.
+        let mut deepest = vec![false; self.state.len()];
+        for i in 0..self.state.len() {
+            if !contested[i] && self.is_structural_counter(i) {
+                deepest[self.state[i].parent_index as usize] = true;
+            }
+        }
+
+        let ranges = (0..self.state.len())
+            .filter(|i| deepest[*i])
+            .map(|i| self.state[i].position.trace_range(self.max_depth))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        Ok(ranges
+            .into_iter()
+            .reduce(|a, b| *a.start().min(b.start())..=*a.end().max(b.end())))
+    }
+
+    /// Summarizes how the contested claims of the game compare against the honest trace, for
+    /// a quick triage of whether the game is a genuine attack or noise. A claim is contested
+    /// if it is effectively countered by another claim, as in
+    /// [FaultDisputeState::contested_window]. Claims are compared ignoring the VM status byte.
+    ///
+    /// ### Takes
+    /// - `provider`: The [TraceProvider] serving the honest trace. It is queried once per
+    ///   contested claim.
+    ///
+    /// ### Returns
+    /// - [DivergenceSummary] or [Err]: The summary, or an error if the provider fails.
+    pub fn divergence_summary<T, P>(&self, provider: &P) -> anyhow::Result<DivergenceSummary>
+    where
+        T: AsRef<[u8]>,
+        P: TraceProvider<T>,
+    {
+        let mut contested = vec![false; self.state.len()];
+        for i in 0..self.state.len() {
+            if self.is_structural_counter(i) {
+                contested[self.state[i].parent_index as usize] = true;
+            }
+        }
+
+        let mut summary = DivergenceSummary::default();
+        for (claim, _) in self.state.iter().zip(contested).filter(|(_, c)| *c) {
+            let honest = provider.state_hash(claim.position)?;
+            if claims_equal_ignoring_status(&honest, &claim.value) {
+                summary.agreeing += 1;
+            } else {
+                summary.disagreeing += 1;
+                let depth = claim.position.depth();
+                summary.shallowest_disagreement = Some(
+                    summary
+                        .shallowest_disagreement
+                        .map_or(depth, |d| d.min(depth)),
+                );
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Checks if bisection is complete along the path from the root claim to the claim at the
+    /// given index. The path is fully bisected if the claim is at the max depth of the position
+    /// tree and every claim on the path effectively counters its parent, meaning the next
+    /// response along the path is a step rather than a move.
+    pub fn is_fully_bisected(&self, path_leaf_index: usize) -> bool {
+        let Some(leaf) = self.state.get(path_leaf_index) else {
+            return false;
+        };
+        if leaf.position.depth() != self.max_depth {
+            return false;
+        }
+
+        let mut index = path_leaf_index;
+        while self.state[index].parent_index != u32::MAX {
+            if !self.is_structural_counter(index) {
+                return false;
+            }
+            index = self.state[index].parent_index as usize;
+        }
+        true
+    }
+
+    /// Computes the move against the claim at `from_claim` that bisects toward a known
+    /// divergent trace index, bypassing the general solver when the fault location is known.
+    /// The claim is attacked if the target is at or before the trace index that it commits to,
+    /// and defended otherwise. Against a leaf claim, the response is a step whose disputed
+    /// transition ends at the target trace index.
+    ///
+    /// ### Takes
+    /// - `provider`: The [TraceProvider] serving the honest trace.
+    /// - `from_claim`: The index of the claim to move against within the state DAG.
+    /// - `target_trace_index`: The first trace index at which the traces diverge.
+    ///
+    /// ### Returns
+    /// - [FaultSolverResponse] or [Err]: The move toward the target trace index.
+    pub fn next_move_toward<T, P>(
+        &self,
+        provider: &P,
+        from_claim: usize,
+        target_trace_index: u64,
+    ) -> anyhow::Result<FaultSolverResponse<T>>
+    where
+        T: AsRef<[u8]>,
+        P: TraceProvider<T>,
+    {
+        let claim = self
+            .state
+            .get(from_claim)
+            .ok_or(anyhow::anyhow!("Claim does not exist"))?;
+        if target_trace_index
+            .checked_shr(self.max_depth as u32)
+            .unwrap_or(0)
+            != 0
+        {
+            anyhow::bail!(
+                "Target trace index {} is beyond the trace",
+                target_trace_index
+            );
+        }
+
+        // The root claim commits to the last trace index, so it is always attacked. A trace
+        // index that does not fit within a u64 is beyond any target, so it is attacked too.
+        let is_attack = claim
+            .position
+            .trace_index(self.max_depth)
+            .map_or(true, |trace_index| target_trace_index <= trace_index);
+
+        if claim.position.depth() == self.max_depth {
+            let (_, pre_state, proof) = Self::step_prestate(provider, claim.position, is_attack)?;
+            Ok(FaultSolverResponse::Step(
+                is_attack, from_claim, pre_state, proof,
+            ))
+        } else {
+            let claim_hash = provider.state_hash(claim.position.make_move(is_attack))?;
+            Ok(FaultSolverResponse::Move(is_attack, from_claim, claim_hash))
+        }
+    }
+
+    /// Resolves everything needed to build a step transaction against the leaf claim at the
+    /// given index: the pre-state of the disputed transition and its proof, and the claim in
+    /// the DAG that commits to the post-state. When attacking, the post-state claim is the
+    /// stepped claim itself. When defending, it is the ancestor of the stepped claim that
+    /// commits to the next trace index, as in the contract.
+    ///
+    /// ### Takes
+    /// - `claim_index`: The index of the leaf claim to step against within the state DAG.
+    /// - `is_attack`: Whether the step is an attack or a defense.
+    /// - `provider`: The [TraceProvider] serving the honest trace.
+    ///
+    /// ### Returns
+    /// - [StepBundle] or [Err]: The resolved step, or an error if the claim is not a leaf,
+    ///   the post-state claim does not exist, or the provider fails.
+    pub fn step_bundle<T, P>(
+        &self,
+        claim_index: usize,
+        is_attack: bool,
+        provider: &P,
+    ) -> anyhow::Result<StepBundle<T>>
+    where
+        T: AsRef<[u8]>,
+        P: TraceProvider<T>,
+    {
+        let claim = self
+            .state
+            .get(claim_index)
+            .ok_or(anyhow::anyhow!("Claim does not exist"))?;
+        if claim.position.depth() != self.max_depth {
+            anyhow::bail!("Claim {} is not at the max depth", claim_index);
+        }
+
+        // Walk up the path from the stepped claim to find the claim committing to the
+        // post-state of the disputed transition.
+        let post_trace_index = claim.position.trace_index(self.max_depth)? + !is_attack as u64;
+        let mut post = Some(claim);
+        while let Some(c) = post {
+            if c.position.trace_index(self.max_depth)? == post_trace_index {
+                break;
+            }
+            post = self.state.get(c.parent_index as usize);
+        }
+        let post = post.ok_or(anyhow::anyhow!(
+            "No claim on the path of claim {} commits to trace index {}",
+            claim_index,
+            post_trace_index
+        ))?;
+
+        let (prestate_pos, prestate_data, prestate_proof) =
+            Self::step_prestate(provider, claim.position, is_attack)?;
+        Ok(StepBundle {
+            prestate_pos,
+            prestate_data,
+            prestate_proof,
+            post_claim: post.value,
+            post_claim_pos: post.position,
+        })
+    }
+
+    /// Fetches the pre-state and proof of a step against the leaf claim at the given position.
+    /// The pre-state of an attack against the first leaf is the absolute prestate, which has
+    /// no position. See [BisectionConvention::step_prestate_position].
+    fn step_prestate<T, P>(
+        provider: &P,
+        position: Position,
+        is_attack: bool,
+    ) -> anyhow::Result<StepPrestate<T>>
+    where
+        T: AsRef<[u8]>,
+        P: TraceProvider<T>,
+    {
+        let Some(pre_state_pos) =
+            BisectionConvention::OpStack.step_prestate_position(position, is_attack)
+        else {
+            return Ok((None, provider.absolute_prestate(), Arc::from([])));
+        };
+        Ok((
+            Some(pre_state_pos),
+            provider.state_at(pre_state_pos)?,
+            provider.proof_at(pre_state_pos)?,
+        ))
+    }
+
+    /// Computes the [StateDiff] between a previous snapshot of the state and the current state.
+    ///
+    /// ### Takes
+    /// - `previous`: A previous snapshot of the same game's state.
+    ///
+    /// ### Returns
+    /// - [StateDiff]: The claims added since `previous` and the previously existing claims
+    ///   that they counter.
+    pub fn diff(&self, previous: &FaultDisputeState) -> StateDiff {
+        let previous_len = previous.state.len();
+        let added = (previous_len..self.state.len()).collect::<Vec<_>>();
+
+        let mut countered = added
+            .iter()
+            .filter_map(|i| {
+                let parent_index = self.state[*i].parent_index as usize;
+                (parent_index < previous_len).then_some(parent_index)
+            })
+            .collect::<Vec<_>>();
+        countered.sort_unstable();
+        countered.dedup();
+
+        StateDiff { added, countered }
+    }
+
+    /// Checks whether the local state has drifted from the chain and needs a full reload,
+    /// e.g. because events were missed. Claims are only ever appended to the on-chain DAG, so
+    /// a mismatch in the number of claims is enough to detect drift.
+    ///
+    /// ### Takes
+    /// - `on_chain_claim_count`: The number of claims in the on-chain DAG, as returned by
+    ///   `claimDataLen()`. See [crate::claim_data_len_calldata].
+    ///
+    /// ### Returns
+    /// - `true` if the local claim count differs from the on-chain claim count.
+    pub fn needs_reload(&self, on_chain_claim_count: usize) -> bool {
+        self.state.len() != on_chain_claim_count
+    }
+
+    /// Computes the smallest set of moves that the honest side must make to win the game,
+    /// rather than countering every claim that the solver disagrees with.
+    ///
+    /// The state DAG is resolved as a tree of subgames, where a claim is countered if any of
+    /// its children are left uncountered. A single counter against an ancestor invalidates the
+    /// whole subtree beneath it, and existing claims that support the local opinion of the root
+    /// claim are reused in place of new moves wherever they lead to fewer moves.
+    ///
+    /// ### Takes
+    /// - `solver`: The [FaultClaimSolver] used to find the response to each claim.
+    /// - `now`: The current timestamp, in seconds. Moves that would not be accepted by the
+    ///   contract at this time are not considered.
+    ///
+    /// ### Returns
+    /// - The minimal set of [FaultSolverResponse]s, ordered by claim index, or an [Err] if
+    ///   the solver fails or there is no set of moves that wins the game.
+    pub fn minimal_winning_moves<T, P, S>(
+        &self,
+        solver: &S,
+        now: u64,
+    ) -> anyhow::Result<Vec<FaultSolverResponse<T>>>
+    where
+        T: AsRef<[u8]>,
+        P: TraceProvider<T>,
+        S: FaultClaimSolver<T, P>,
+    {
+        if self.state.is_empty() {
+            anyhow::bail!("Game has no root claim");
+        }
+        let attacking_root = solver.state_hash(Position::ROOT)? != self.root_claim;
+
+        let mut responses = (0..self.state.len())
+            .map(|i| solver.solve_claim(self, i, attacking_root).map(Some))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+
+        // Only effective counters take part in the resolution of their parent's subgame.
+        let root = *self
+            .topological_order()?
+            .first()
+            .ok_or(anyhow::anyhow!("No set of moves wins the game"))?;
+        let mut children = self.children_indices()?;
+        children
+            .iter_mut()
+            .for_each(|c| c.retain(|i| self.is_structural_counter(*i)));
+
+        // If the solver supports the root claim, its subgame must be left uncountered.
+        // Otherwise, it must be countered.
+        let minimal = if matches!(responses[root], Some(FaultSolverResponse::Skip(_))) {
+            self.minimal_uncountered(root, &children, &responses, now)
+        } else {
+            self.minimal_countered(root, &children, &responses, now)
+        };
+        let mut minimal = minimal.ok_or(anyhow::anyhow!("No set of moves wins the game"))?;
+        minimal.sort_unstable();
+
+        Ok(minimal
+            .into_iter()
+            .filter_map(|i| responses[i].take())
+            .collect())
+    }
+
+    /// Estimates the number of moves and steps that the honest side makes to win the game, by
+    /// playing out the honest solver's responses against a scratch copy of the state until
+    /// every claim has been answered. The opponent is assumed to make no further moves, and
+    /// moves that would not be accepted by the contract are not counted.
+    ///
+    /// ### Takes
+    /// - `solver`: The [FaultClaimSolver] used to find the response to each claim.
+    /// - `now`: The current timestamp, in seconds.
+    ///
+    /// ### Returns
+    /// - `usize` or [Err]: The number of non-skip responses, or an error if the solver fails.
+    pub fn estimated_moves_to_win<T, P, S>(&self, solver: &S, now: u64) -> anyhow::Result<usize>
+    where
+        T: AsRef<[u8]>,
+        P: TraceProvider<T>,
+        S: FaultClaimSolver<T, P>,
+    {
+        if self.state.is_empty() {
+            anyhow::bail!("Game has no root claim");
+        }
+        let attacking_root = solver.state_hash(Position::ROOT)? != self.root_claim;
+
+        let mut scratch = self.clone();
+        scratch.state.iter_mut().for_each(|c| c.visited = false);
+
+        // Each move made by the honest side is a new claim to be answered, so solve until
+        // every claim has been visited.
+        let mut moves = 0;
+        let mut next = 0;
+        while next < scratch.state.len() {
+            let index = next;
+            next += 1;
+
+            // Claims that do not counter their parent are not answered.
+            if scratch.state[index].parent_index != u32::MAX
+                && !scratch.is_structural_counter(index)
+            {
+                continue;
+            }
+
+            match solver.solve_claim(&scratch, index, attacking_root)? {
+                FaultSolverResponse::Move(is_attack, parent_index, value) => {
+                    if scratch.is_move_legal(parent_index, is_attack, now).is_ok() {
+                        let position = scratch.state[parent_index].position.make_move(is_attack);
+                        scratch.state.push(ClaimData {
+                            parent_index: parent_index as u32,
+                            visited: false,
+                            value,
+                            position,
+                            clock: now as u128,
+                            claimant: Address::ZERO,
+                            countered_by: Address::ZERO,
+                        });
+                        moves += 1;
+                    }
+                }
+                FaultSolverResponse::Step(..) => moves += 1,
+                FaultSolverResponse::Skip(_)
+                | FaultSolverResponse::Defer(_)
+                | FaultSolverResponse::Resolve(_) => {}
+            }
+        }
+        Ok(moves)
+    }
+
+    /// Returns the indices of the claims that must be countered, at a minimum, for the claim at
+    /// the given index to be countered, or [None] if it cannot be countered.
+    fn minimal_countered<T: AsRef<[u8]>>(
+        &self,
+        index: usize,
+        children: &[Vec<usize>],
+        responses: &[Option<FaultSolverResponse<T>>],
+        now: u64,
+    ) -> Option<Vec<usize>> {
+        // The claim may be countered directly by the solver's response to it, if the move
+        // would be accepted by the contract.
+        let direct = match responses[index] {
+            Some(FaultSolverResponse::Move(is_attack, _, _)) => self
+                .is_move_legal(index, is_attack, now)
+                .is_ok()
+                .then(|| vec![index]),
+            Some(FaultSolverResponse::Step(..)) => Some(vec![index]),
+            _ => None,
+        };
+
+        // Or by any existing child that can be left uncountered.
+        children[index]
+            .iter()
+            .filter_map(|c| self.minimal_uncountered(*c, children, responses, now))
+            .chain(direct)
+            .min_by_key(|moves| moves.len())
+    }
+
+    /// Returns the indices of the claims that must be countered, at a minimum, for the claim at
+    /// the given index to be left uncountered, or [None] if it cannot be left uncountered.
+    fn minimal_uncountered<T: AsRef<[u8]>>(
+        &self,
+        index: usize,
+        children: &[Vec<usize>],
+        responses: &[Option<FaultSolverResponse<T>>],
+        now: u64,
+    ) -> Option<Vec<usize>> {
+        // Claims that the solver disagrees with are never left uncountered.
+        if !matches!(responses[index], Some(FaultSolverResponse::Skip(_))) {
+            return None;
+        }
+
+        // Every child countering the claim must itself be countered.
+        let mut moves = Vec::new();
+        for child in &children[index] {
+            moves.extend(self.minimal_countered(*child, children, responses, now)?);
+        }
+        Some(moves)
+    }
+
+    /// Builds a nested [TreeView] of the state DAG, rooted at the root claim.
+    ///
+    /// ### Returns
+    /// - [TreeView] or [Err]: The view of the root claim's subtree, or an error if the state
+    ///   DAG is empty or malformed.
+    pub fn to_tree_view(&self) -> anyhow::Result<TreeView> {
+        let order = self.topological_order()?;
+        let Some(root) = order.first().copied() else {
+            anyhow::bail!("State DAG is empty");
+        };
+
+        let countered = self.resolved_countered(&order);
+        Ok(self.tree_view_at(root, &self.children_indices()?, &countered))
+    }
+
+    /// Builds the [TreeView] of the subtree rooted at the claim at the given index.
+    fn tree_view_at(&self, index: usize, children: &[Vec<usize>], countered: &[bool]) -> TreeView {
+        let claim = &self.state[index];
+        TreeView {
+            position: claim.position,
+            depth: claim.position.depth(),
+            value: claim.value,
+            claimant: claim.claimant,
+            countered: countered[index],
+            children: children[index]
+                .iter()
+                .map(|c| self.tree_view_at(*c, children, countered))
+                .collect(),
+        }
+    }
+
+    /// Renders the state DAG as a Graphviz DOT graph. Each claim is a node labeled with its
+    /// index, position, and truncated value, with an edge from each parent claim to its
+    /// children. Claims that are countered once the game's subgames are resolved, as in
+    /// [DisputeGame::resolve], are colored red.
+    ///
+    /// ### Returns
+    /// - [String] or [Err]: The DOT graph, or an error if the state DAG is malformed.
+    pub fn to_dot(&self) -> anyhow::Result<String> {
+        let countered = self.resolved_countered(&self.topological_order()?);
+
+        let mut dot = String::from("digraph FaultDisputeGame {\n");
+        for (i, claim) in self.state.iter().enumerate() {
+            let value = claim.value.to_string();
+            let _ = writeln!(
+                dot,
+                "    {i} [label=\"#{i}\\npos: {}\\n{}..\"{}];",
+                claim.position,
+                &value[..10],
+                if countered[i] { ", color=red" } else { "" }
+            );
+        }
+        for (i, claim) in self.state.iter().enumerate() {
+            if claim.parent_index != u32::MAX {
+                let _ = writeln!(dot, "    {} -> {i};", claim.parent_index);
+            }
+        }
+        dot.push('}');
+        Ok(dot)
     }
 }
 
@@ -60,8 +1486,26 @@ impl DisputeGame for FaultDisputeState {
         &self.status
     }
 
-    fn resolve(&mut self) -> &GameStatus {
-        &self.status
+    fn resolve(&mut self, sim: bool) -> anyhow::Result<GameStatus> {
+        let status = self.resolved_status()?;
+        if !sim {
+            self.status = status.clone();
+        }
+        Ok(status)
+    }
+}
+
+impl ClaimDag for FaultDisputeState {
+    fn len(&self) -> usize {
+        self.state.len()
+    }
+
+    fn claim(&self, index: usize) -> Option<ClaimData> {
+        self.state.get(index).copied()
+    }
+
+    fn max_depth(&self) -> u8 {
+        self.max_depth
     }
 }
 
@@ -74,3 +1518,1648 @@ impl FaultDisputeGame for FaultDisputeState {
         &mut self.state
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        AlphaClaimSolver, AlphabetTraceProvider, FaultDisputeSolver, FixtureTraceProvider,
+        FnTraceProvider,
+    };
+    use alloy_primitives::{keccak256, U256};
+    use durin_primitives::DisputeSolver;
+    use proptest::{prelude::*, sample::Index};
+
+    fn mock_state() -> FaultDisputeState {
+        FaultDisputeState::new(
+            vec![
+                ClaimData {
+                    parent_index: u32::MAX,
+                    visited: false,
+                    value: Claim::ZERO,
+                    position: Position::from(1),
+                    clock: 0,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 0,
+                    visited: false,
+                    value: Claim::ZERO,
+                    position: Position::from(2),
+                    clock: 10,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+                ClaimData {
+                    parent_index: 1,
+                    visited: false,
+                    value: Claim::ZERO,
+                    position: Position::from(4),
+                    clock: (5 << 64) | 20,
+                    claimant: Address::ZERO,
+                    countered_by: Address::ZERO,
+                },
+            ],
+            Claim::ZERO,
+            GameStatus::InProgress,
+            3,
+        )
+    }
+
+    #[test]
+    fn resolve_with_invalid_anchor() {
+        let mut state = mock_state();
+        assert_eq!(
+            state.resolve_with_anchor(false, true).unwrap(),
+            GameStatus::ChallengerWins
+        );
+        assert_eq!(state.status(), &GameStatus::InProgress);
+
+        assert_eq!(
+            state.resolve_with_anchor(false, false).unwrap(),
+            GameStatus::ChallengerWins
+        );
+        assert_eq!(state.status(), &GameStatus::ChallengerWins);
+    }
+
+    #[test]
+    fn resolve_with_rules() {
+        let rules = || {
+            (
+                vec![crate::rules::resolved_subgames_consistent()],
+                vec![
+                    crate::rules::resolved_subgames_consistent(),
+                    crate::rules::root_status_terminal(),
+                ],
+            )
+        };
+
+        let mut state = mock_state();
+        let (pre, post) = rules();
+        assert_eq!(
+            state.resolve_with_rules(&pre, &post).unwrap(),
+            GameStatus::DefenderWins
+        );
+        assert_eq!(state.status(), &GameStatus::DefenderWins);
+
+        // A claim that is resolved while its child is not.
+        let mut state = mock_state();
+        state.mark_subgame_resolved(1);
+        assert_eq!(
+            state
+                .resolve_with_rules(&pre, &post)
+                .unwrap_err()
+                .to_string(),
+            "Claim 1 is resolved, but the subgame of its child 2 is not"
+        );
+        assert_eq!(state.status(), &GameStatus::InProgress);
+
+        // An empty state DAG cannot reach a terminal status.
+        let mut state = FaultDisputeState::new(Vec::new(), Claim::ZERO, GameStatus::InProgress, 3);
+        assert_eq!(
+            state
+                .resolve_with_rules(&pre, &post)
+                .unwrap_err()
+                .to_string(),
+            "Root status is not terminal"
+        );
+        assert_eq!(
+            state.resolve_with_rules(&pre, &[]).unwrap(),
+            GameStatus::InProgress
+        );
+
+        // A violated post rule sees the resolved status, which is then rolled back.
+        let mut state = mock_state();
+        let post: Vec<ResolutionRule> = vec![Box::new(|state: &FaultDisputeState| {
+            anyhow::bail!("Resolved to {:?}", state.status())
+        })];
+        assert_eq!(
+            state
+                .resolve_with_rules(&[], &post)
+                .unwrap_err()
+                .to_string(),
+            "Resolved to DefenderWins"
+        );
+        assert_eq!(state.status(), &GameStatus::InProgress);
+    }
+
+    #[test]
+    fn resolve_with_valid_anchor() {
+        let mut state = mock_state();
+        let expected = state.clone().resolve(false).unwrap();
+        assert_eq!(state.resolve_with_anchor(true, true).unwrap(), expected);
+        assert_eq!(state.resolve_with_anchor(true, false).unwrap(), expected);
+        assert_eq!(state.status(), &expected);
+    }
+
+    #[test]
+    fn resolve_subgames() {
+        // The uncountered claim 2 counters claim 1, leaving the root uncountered.
+        let mut state = mock_state();
+        assert_eq!(state.resolve(false).unwrap(), GameStatus::DefenderWins);
+        assert_eq!(state.status(), &GameStatus::DefenderWins);
+
+        state.state_mut().push(ClaimData {
+            parent_index: 2,
+            visited: false,
+            value: Claim::ZERO,
+            position: Position::from(8),
+            clock: 0,
+            claimant: Address::ZERO,
+            countered_by: Address::ZERO,
+        });
+        assert_eq!(state.resolve(false).unwrap(), GameStatus::ChallengerWins);
+
+        // A claim that does not counter its parent does not affect resolution.
+        state.state_mut().push(ClaimData {
+            parent_index: 3,
+            visited: false,
+            value: Claim::ZERO,
+            position: Position::from(8),
+            clock: 0,
+            claimant: Address::ZERO,
+            countered_by: Address::ZERO,
+        });
+        assert_eq!(state.resolve(false).unwrap(), GameStatus::ChallengerWins);
+    }
+
+    #[test]
+    fn resolve_sim() {
+        let mut state = mock_state();
+        state.state.truncate(1);
+        assert_eq!(state.resolve(true).unwrap(), GameStatus::DefenderWins);
+        assert_eq!(state.status(), &GameStatus::InProgress);
+
+        // An uncountered attack against the root.
+        state
+            .state
+            .push(alphabet_claim(0, Position::from(2), Claim::ZERO));
+        assert_eq!(state.resolve(true).unwrap(), GameStatus::ChallengerWins);
+        assert_eq!(state.status(), &GameStatus::InProgress);
+        assert_eq!(state.resolve(false).unwrap(), GameStatus::ChallengerWins);
+        assert_eq!(state.status(), &GameStatus::ChallengerWins);
+    }
+
+    #[test]
+    fn resolve_stepped_claims() {
+        // Without a step, the leaf claim 2 counters claim 1, and the defender wins.
+        let mut state = mock_state();
+        assert_eq!(state.resolve(true).unwrap(), GameStatus::DefenderWins);
+
+        // A step against the leaf claim 2 leaves claim 1 uncountered.
+        state.state[2].countered_by = Address::repeat_byte(0x01);
+        assert_eq!(state.resolve(false).unwrap(), GameStatus::ChallengerWins);
+
+        // A defense against claim 1 counters it again, regardless of the step.
+        state
+            .state
+            .push(alphabet_claim(1, Position::from(6), Claim::ZERO));
+        assert_eq!(state.resolve(false).unwrap(), GameStatus::DefenderWins);
+    }
+
+    /// A brute-force reference resolver. A claim is uncountered if every one of its children
+    /// that effectively counters it is countered. A child effectively counters its parent if it
+    /// sits at the parent's left child, or at the right child of the parent's successor when
+    /// the parent is not the root, within the max depth.
+    fn reference_uncountered(state: &FaultDisputeState, index: usize) -> bool {
+        let parent = &state.state[index];
+        let attack = parent.position.left();
+        let defense = (parent.position + 1).left();
+        (0..state.state.len())
+            .filter(|c| state.state[*c].parent_index as usize == index)
+            .filter(|c| {
+                let position = state.state[*c].position;
+                position.depth() <= state.max_depth
+                    && (position == attack
+                        || (position == defense && parent.parent_index != u32::MAX))
+            })
+            .all(|c| !reference_uncountered(state, c))
+    }
+
+    proptest! {
+        #[test]
+        fn resolve_matches_reference(
+            moves in prop::collection::vec(
+                (any::<Index>(), any::<bool>(), prop::option::weighted(0.25, 1u128..32)),
+                0..32,
+            )
+        ) {
+            let mut state = FaultDisputeState::new(
+                vec![alphabet_claim(u32::MAX, Position::ROOT, Claim::ZERO)],
+                Claim::ZERO,
+                GameStatus::InProgress,
+                3,
+            );
+
+            // Apply each legal move against a random parent, skipping illegal moves. Claims
+            // with an explicit position are placed regardless of their parent, and most of
+            // them do not counter it.
+            for (parent, is_attack, position) in moves {
+                let parent_index = parent.index(state.state.len());
+                let position = match position {
+                    Some(position) => Position::from(position),
+                    None if state.is_move_legal(parent_index, is_attack, 0).is_ok() => {
+                        state.state[parent_index].position.make_move(is_attack)
+                    }
+                    None => continue,
+                };
+                state
+                    .state
+                    .push(alphabet_claim(parent_index as u32, position, Claim::ZERO));
+            }
+
+            let expected = if reference_uncountered(&state, 0) {
+                GameStatus::DefenderWins
+            } else {
+                GameStatus::ChallengerWins
+            };
+            prop_assert_eq!(state.resolve(false).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn sibling_claim() {
+        let mut state = mock_state();
+        state.state.extend([
+            // A defense against claim 1, alongside the attack at claim 2.
+            ClaimData {
+                parent_index: 1,
+                visited: false,
+                value: Claim::ZERO,
+                position: Position::from(6),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            },
+            // A claim that is not a move against its parent.
+            ClaimData {
+                parent_index: 1,
+                visited: false,
+                value: Claim::ZERO,
+                position: Position::from(5),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            },
+        ]);
+
+        assert_eq!(state.sibling_claim(2), Some(3));
+        assert_eq!(state.sibling_claim(3), Some(2));
+        assert_eq!(state.sibling_claim(1), None);
+        assert_eq!(state.sibling_claim(0), None);
+        assert_eq!(state.sibling_claim(4), None);
+        assert_eq!(state.sibling_claim(5), None);
+    }
+
+    #[test]
+    fn divergence_depth() {
+        let mut state = mock_state();
+        state.state.extend([
+            alphabet_claim(1, Position::from(5), Claim::ZERO),
+            alphabet_claim(1, Position::from(6), Claim::ZERO),
+            alphabet_claim(2, Position::from(9), Claim::ZERO),
+        ]);
+
+        // Siblings diverge at the depth of their parent.
+        assert_eq!(state.divergence_depth(2, 3), Some(1));
+        assert_eq!(state.divergence_depth(2, 5), Some(2));
+        assert_eq!(state.divergence_depth(3, 5), Some(1));
+        // Claims on opposite sides of the root diverge at the root.
+        assert_eq!(state.divergence_depth(2, 4), Some(0));
+        // A claim's ancestor is their common ancestor.
+        assert_eq!(state.divergence_depth(0, 5), Some(0));
+        assert_eq!(state.divergence_depth(5, 1), Some(1));
+        assert_eq!(state.divergence_depth(2, 2), Some(2));
+        assert_eq!(state.divergence_depth(0, 6), None);
+    }
+
+    #[test]
+    fn pivotal_claim() {
+        // Claim 2 counters claim 1, leaving the root uncountered. Countering claim 2 is the
+        // only way for a challenger to counter the root, as attacking the root again is not
+        // allowed.
+        let mut state = mock_state();
+        let challenger_opinion = Claim::repeat_byte(0x01);
+        assert_eq!(state.pivotal_claim(100, challenger_opinion), Some(2));
+        // The game already resolves to the defender's opinion.
+        assert_eq!(state.pivotal_claim(100, Claim::ZERO), None);
+        // Claim 2 can no longer be countered once its clock has expired.
+        assert_eq!(
+            state.pivotal_claim(state.game_duration, challenger_opinion),
+            None
+        );
+
+        // With a second uncountered defense against claim 1, no single counter flips the root.
+        state
+            .state
+            .push(alphabet_claim(1, Position::from(6), Claim::ZERO));
+        assert_eq!(state.pivotal_claim(100, challenger_opinion), None);
+    }
+
+    #[test]
+    fn resolve_calldata_batch() {
+        // A branching DAG whose claims are not stored in topological order.
+        let mut state = mock_state();
+        state.state.extend([
+            alphabet_claim(4, Position::from(12), Claim::ZERO),
+            alphabet_claim(1, Position::from(6), Claim::ZERO),
+            alphabet_claim(2, Position::from(8), Claim::ZERO),
+        ]);
+        state.mark_subgame_resolved(5);
+
+        let batch = state.resolve_calldata_batch().unwrap();
+        let (resolve, resolve_claims) = batch.split_last().unwrap();
+        assert_eq!(resolve[..], keccak256("resolve()")[..4]);
+        let order = resolve_claims
+            .iter()
+            .map(|calldata| {
+                assert_eq!(calldata[..4], keccak256("resolveClaim(uint256)")[..4]);
+                U256::try_from_be_slice(&calldata[4..])
+                    .unwrap()
+                    .to::<usize>()
+            })
+            .collect::<Vec<_>>();
+
+        // Every subgame that is not yet resolved is resolved once, after its children.
+        assert_eq!(order.len(), 5);
+        assert!(!order.contains(&5));
+        let at = |i| order.iter().position(|o| *o == i).unwrap();
+        for (i, claim) in state.state.iter().enumerate().filter(|(i, _)| *i != 5) {
+            if claim.parent_index != u32::MAX {
+                assert!(at(i) < at(claim.parent_index as usize));
+            }
+        }
+        assert_eq!(order.last(), Some(&0));
+    }
+
+    #[test]
+    fn needs_step() {
+        let honest = Claim::repeat_byte(0x01);
+        let mut state = mock_state();
+        state.state.extend([
+            alphabet_claim(2, Position::from(8), honest),
+            alphabet_claim(2, Position::from(9), Claim::repeat_byte(0x02)),
+            alphabet_claim(2, Position::from(15), honest),
+        ]);
+
+        // A right leaf is defended, and a wrong leaf is attacked.
+        assert_eq!(state.needs_step(3, honest), Some(false));
+        assert_eq!(state.needs_step(4, honest), Some(true));
+        // A leaf that only differs in its VM status byte is wrong.
+        let mut status = honest;
+        status[0] = 0x02;
+        assert_eq!(state.needs_step(3, status), Some(true));
+        // A right leaf at the last trace index has no next instruction to dispute.
+        assert_eq!(state.needs_step(5, honest), None);
+        assert_eq!(state.needs_step(5, Claim::ZERO), Some(true));
+        // Claims above the max depth and missing claims cannot be stepped against.
+        assert_eq!(state.needs_step(2, honest), None);
+        assert_eq!(state.needs_step(6, honest), None);
+
+        // At the mainnet max game depth of 73, the last trace index does not fit within a u64.
+        let max_depth = 73;
+        let last_leaf = Position::ROOT.right_index(max_depth);
+        let state = FaultDisputeState::new(
+            vec![
+                alphabet_claim(u32::MAX, Position::ROOT, honest),
+                alphabet_claim(0, crate::compute_gindex(max_depth, 0), honest),
+                alphabet_claim(0, last_leaf - 1, honest),
+                alphabet_claim(0, last_leaf, honest),
+            ],
+            honest,
+            GameStatus::InProgress,
+            max_depth,
+        );
+        assert_eq!(state.needs_step(1, honest), Some(false));
+        assert_eq!(state.needs_step(2, honest), Some(false));
+        assert_eq!(state.needs_step(3, honest), None);
+        assert_eq!(state.needs_step(3, Claim::ZERO), Some(true));
+    }
+
+    #[test]
+    fn contested_window() {
+        let mut state = mock_state();
+        state.state.truncate(1);
+        assert_eq!(state.contested_window().unwrap(), None);
+
+        // A single disputed path narrows down to its deepest contested claim, claim 1.
+        let mut state = mock_state();
+        assert_eq!(state.contested_window().unwrap(), Some(0..=3));
+
+        // A second disputed path, beneath a defense against claim 1, widens the window.
+        state.state.extend([
+            alphabet_claim(1, Position::from(6), Claim::ZERO),
+            alphabet_claim(3, Position::from(12), Claim::ZERO),
+        ]);
+        assert_eq!(state.contested_window().unwrap(), Some(0..=5));
+
+        // A claim that does not counter its parent does not contest it.
+        state
+            .state
+            .push(alphabet_claim(2, Position::from(9), Claim::ZERO));
+        assert_eq!(state.contested_window().unwrap(), Some(0..=5));
+    }
+
+    #[test]
+    fn divergence_summary() {
+        let provider = AlphabetTraceProvider::new(b'a', 4);
+        let honest = |position: u128| provider.state_hash(Position::from(position)).unwrap();
+        let mut state = FaultDisputeState::new(
+            vec![alphabet_claim(u32::MAX, Position::from(1), honest(1))],
+            honest(1),
+            GameStatus::InProgress,
+            4,
+        );
+        assert_eq!(
+            state.divergence_summary(&provider).unwrap(),
+            DivergenceSummary::default()
+        );
+
+        // Noise: honest claims countering honest claims, along with a single genuine
+        // disagreement at claim 4, which is countered by claim 5.
+        state.state.extend([
+            alphabet_claim(0, Position::from(2), honest(2)),
+            alphabet_claim(1, Position::from(4), honest(4)),
+            alphabet_claim(2, Position::from(8), honest(8)),
+            alphabet_claim(1, Position::from(6), Claim::repeat_byte(0xFF)),
+            alphabet_claim(4, Position::from(12), honest(12)),
+            alphabet_claim(2, Position::from(10), honest(10)),
+            alphabet_claim(6, Position::from(20), honest(20)),
+        ]);
+        assert_eq!(
+            state.divergence_summary(&provider).unwrap(),
+            DivergenceSummary {
+                agreeing: 4,
+                disagreeing: 1,
+                shallowest_disagreement: Some(2),
+            }
+        );
+    }
+
+    #[test]
+    fn root_invariants() {
+        let mut state = mock_state();
+        assert!(state.assert_root_invariants().is_ok());
+
+        state.state[0].parent_index = 2;
+        assert_eq!(
+            state.assert_root_invariants().unwrap_err().to_string(),
+            "Root claim has parent index 2"
+        );
+
+        state.state.swap(0, 1);
+        assert_eq!(
+            state.assert_root_invariants().unwrap_err().to_string(),
+            "Root claim is at position 2, expected 1"
+        );
+
+        state.state.clear();
+        assert!(state.assert_root_invariants().is_ok());
+    }
+
+    #[test]
+    fn resolve_out_of_order_parents() {
+        let mut state = mock_state();
+        for (parent_index, is_attack) in [(2, true), (1, false), (4, true), (4, false)] {
+            let position = state.state[parent_index].position.make_move(is_attack);
+            state.state.push(ClaimData {
+                parent_index: parent_index as u32,
+                visited: false,
+                value: Claim::ZERO,
+                position,
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            });
+        }
+        let expected = state.clone().resolve(false).unwrap();
+
+        // Reverse the claims, so that every claim references a parent after it.
+        let len = state.state.len();
+        let mut shuffled = state.clone();
+        shuffled.state.reverse();
+        shuffled
+            .state
+            .iter_mut()
+            .filter(|c| c.parent_index != u32::MAX)
+            .for_each(|c| c.parent_index = (len - 1 - c.parent_index as usize) as u32);
+
+        let order = shuffled.topological_order().unwrap();
+        assert_eq!(order.first(), Some(&(len - 1)));
+        for (i, claim) in order.iter().enumerate() {
+            let parent_index = shuffled.state[*claim].parent_index;
+            if parent_index != u32::MAX {
+                assert!(order[..i].contains(&(parent_index as usize)));
+            }
+        }
+        assert_eq!(shuffled.resolve(false).unwrap(), expected);
+    }
+
+    #[test]
+    fn resolve_malformed_parents() {
+        let mut state = mock_state();
+        state.state[1].parent_index = 2;
+        assert_eq!(
+            state.topological_order().unwrap_err().to_string(),
+            "Claim 1 is part of a cycle"
+        );
+        assert_eq!(
+            state
+                .resolve_with_anchor(true, false)
+                .unwrap_err()
+                .to_string(),
+            "Claim 1 is part of a cycle"
+        );
+        assert_eq!(
+            state.resolve(false).unwrap_err().to_string(),
+            "Claim 1 is part of a cycle"
+        );
+        assert_eq!(state.status(), &GameStatus::InProgress);
+
+        state.state[1].parent_index = 42;
+        assert_eq!(
+            state.topological_order().unwrap_err().to_string(),
+            "Claim 1 references missing parent 42"
+        );
+
+        state.state[1].parent_index = u32::MAX;
+        assert_eq!(
+            state.topological_order().unwrap_err().to_string(),
+            "State DAG has 2 root claims"
+        );
+    }
+
+    #[test]
+    fn at_risk_claims() {
+        let mut state = mock_state();
+        let half_duration = state.game_duration >> 1;
+        state.state_mut().extend([
+            // A defense against claim 1, made later than claim 2.
+            ClaimData {
+                parent_index: 1,
+                visited: false,
+                value: Claim::ZERO,
+                position: Position::from(6),
+                clock: 100,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            },
+            // A claim that does not counter its parent.
+            ClaimData {
+                parent_index: 1,
+                visited: false,
+                value: Claim::ZERO,
+                position: Position::from(2),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            },
+        ]);
+
+        // Agreeing with the root claim, the claims at odd depths must be countered. Claim 1 is
+        // countered by claim 2, leaving no claim to counter.
+        assert!(state
+            .at_risk_claims(0, half_duration, Claim::ZERO)
+            .is_empty());
+
+        // Disagreeing with the root claim, the root and the claims at even depths must be
+        // countered. The root is countered by claim 1, and the clocks of claims 2 and 3 expire
+        // half a game after they were made, as no time has been spent on them.
+        let bad_opinion = Claim::repeat_byte(0xFF);
+        assert!(state
+            .at_risk_claims(0, half_duration, bad_opinion)
+            .is_empty());
+        assert_eq!(
+            state.at_risk_claims(0, 100 + half_duration, bad_opinion),
+            vec![(2, 20 + half_duration), (3, 100 + half_duration)]
+        );
+        assert_eq!(
+            state.at_risk_claims(50, half_duration - 30, bad_opinion),
+            vec![(2, 20 + half_duration)]
+        );
+
+        // Once a clock has expired, the claim can no longer be countered.
+        assert_eq!(
+            state.at_risk_claims(21 + half_duration, 100, bad_opinion),
+            vec![(3, 100 + half_duration)]
+        );
+
+        // A claim countered by a step is not at risk.
+        state.state_mut()[2].countered_by = Address::with_last_byte(1);
+        assert_eq!(
+            state.at_risk_claims(0, 100 + half_duration, bad_opinion),
+            vec![(3, 100 + half_duration)]
+        );
+
+        // A claim at the zero position is not within the tree, and is never at risk.
+        state
+            .state_mut()
+            .push(alphabet_claim(0, Position::from(0), Claim::ZERO));
+        assert_eq!(
+            state.at_risk_claims(0, 100 + half_duration, bad_opinion),
+            vec![(3, 100 + half_duration)]
+        );
+    }
+
+    #[test]
+    fn freeze_period_pauses_clocks() {
+        let mut state = mock_state();
+        let half_duration = state.game_duration >> 1;
+        let bad_opinion = Claim::repeat_byte(0xFF);
+
+        // Without a freeze period, the clock of a move against claim 2 expires half a game
+        // after it was made.
+        let now = 20 + half_duration + 500;
+        assert_eq!(
+            state.is_move_legal(2, true, now).unwrap_err().to_string(),
+            "Clock time exceeded"
+        );
+        assert_eq!(
+            state.at_risk_claims(0, u64::MAX, bad_opinion),
+            vec![(2, 20 + half_duration)]
+        );
+
+        // A freeze period that starts before the clock expires pauses it, pushing back its
+        // expiry by the time spent within the freeze period.
+        state.freeze_start = 10 + half_duration;
+        state.freeze_duration = 1_000;
+        assert!(state.is_move_legal(2, true, now).is_ok());
+        assert_eq!(
+            state.at_risk_claims(0, u64::MAX, bad_opinion),
+            vec![(2, 1_020 + half_duration)]
+        );
+        assert_eq!(
+            state.resolve_subgame(2, now).unwrap_err().to_string(),
+            "Clock not expired"
+        );
+
+        // A freeze period that starts after the clock expires has no effect.
+        state.freeze_start = 21 + half_duration;
+        assert!(state.is_move_legal(2, true, now).is_err());
+        assert_eq!(
+            state.at_risk_claims(0, u64::MAX, bad_opinion),
+            vec![(2, 20 + half_duration)]
+        );
+
+        // A parent clock with the maximum duration saturates rather than overflowing.
+        state.state_mut()[1].clock = ((u64::MAX as u128) << 64) | 10;
+        assert_eq!(
+            state.is_move_legal(2, true, now).unwrap_err().to_string(),
+            "Clock time exceeded"
+        );
+    }
+
+    #[test]
+    fn resolve_subgame_ordering() {
+        let mut state = mock_state();
+        for (i, claim) in state.state_mut().iter_mut().enumerate() {
+            claim.claimant = Address::with_last_byte(i as u8 + 1);
+        }
+        let now = state.game_duration;
+
+        // Subgames must be resolved bottom-up.
+        assert_eq!(
+            state.resolve_subgame(1, now).unwrap_err().to_string(),
+            "Subgame children are not resolved"
+        );
+        assert_eq!(
+            state.resolve_subgame(0, now).unwrap_err().to_string(),
+            "Subgame children are not resolved"
+        );
+
+        assert!(!state.resolve_subgame(2, now).unwrap());
+        assert!(state.resolve_subgame(1, now).unwrap());
+        assert_eq!(state.state()[1].countered_by, Address::with_last_byte(3));
+        assert!(!state.resolve_subgame(0, now).unwrap());
+        assert_eq!(state.state()[0].countered_by, Address::ZERO);
+        assert!((0..3).all(|i| state.is_subgame_resolved(i)));
+
+        assert_eq!(
+            state.resolve_subgame(2, now).unwrap_err().to_string(),
+            "Subgame already resolved"
+        );
+        assert_eq!(
+            state.resolve_subgame(3, now).unwrap_err().to_string(),
+            "Claim does not exist"
+        );
+    }
+
+    #[test]
+    fn resolve_subgame_clock_not_expired() {
+        let mut state = mock_state();
+        let half_duration = state.game_duration >> 1;
+
+        // Claim 2 was made at 20, and its parent's clock has a duration of 0.
+        assert_eq!(
+            state
+                .resolve_subgame(2, 19 + half_duration)
+                .unwrap_err()
+                .to_string(),
+            "Clock not expired"
+        );
+        assert!(!state.is_subgame_resolved(2));
+        assert!(state.resolve_subgame(2, 20 + half_duration).is_ok());
+    }
+
+    #[test]
+    fn move_legal() {
+        let state = mock_state();
+        assert!(state.is_move_legal(1, false, 10).is_ok());
+        assert!(state.is_move_legal(2, true, 20).is_ok());
+    }
+
+    #[test]
+    fn move_illegal_missing_parent() {
+        let state = mock_state();
+        let err = state.is_move_legal(3, true, 0).unwrap_err();
+        assert_eq!(err.to_string(), "Parent claim does not exist");
+    }
+
+    #[test]
+    fn move_illegal_defend_root() {
+        let state = mock_state();
+        let err = state.is_move_legal(0, false, 0).unwrap_err();
+        assert_eq!(err.to_string(), "Cannot defend the root claim");
+    }
+
+    #[test]
+    fn move_illegal_depth_exceeded() {
+        let mut state = mock_state();
+        state.max_depth = 2;
+        let err = state.is_move_legal(2, true, 20).unwrap_err();
+        assert_eq!(err.to_string(), "Game depth exceeded");
+    }
+
+    #[test]
+    fn move_illegal_claim_exists() {
+        let state = mock_state();
+        let err = state.is_move_legal(0, true, 0).unwrap_err();
+        assert_eq!(err.to_string(), "Claim already exists");
+    }
+
+    #[test]
+    fn move_illegal_clock_expired() {
+        let state = mock_state();
+        let half_duration = state.game_duration >> 1;
+
+        // The grandparent of claim 2 has not spent any time on its clock.
+        assert!(state.is_move_legal(2, true, 20 + half_duration).is_ok());
+        let err = state
+            .is_move_legal(2, true, 21 + half_duration)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Clock time exceeded");
+
+        // The grandparent of a move against claim 1 is the root claim.
+        let mut state = mock_state();
+        state.state_mut()[0].clock = 100 << 64;
+        assert!(state
+            .is_move_legal(1, false, 10 + half_duration - 100)
+            .is_ok());
+        let err = state
+            .is_move_legal(1, false, 11 + half_duration - 100)
+            .unwrap_err();
+        assert_eq!(err.to_string(), "Clock time exceeded");
+    }
+
+    #[test]
+    fn structural_counters() {
+        let mut state = mock_state();
+        state.state_mut().extend([
+            // Defense of the root claim
+            ClaimData {
+                parent_index: 0,
+                visited: false,
+                value: Claim::ZERO,
+                position: Position::from(3),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            },
+            // Defense against claim 1
+            ClaimData {
+                parent_index: 1,
+                visited: false,
+                value: Claim::ZERO,
+                position: Position::from(6),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            },
+            // Not a move against claim 2
+            ClaimData {
+                parent_index: 2,
+                visited: false,
+                value: Claim::ZERO,
+                position: Position::from(7),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            },
+            // Parent does not exist
+            ClaimData {
+                parent_index: 42,
+                visited: false,
+                value: Claim::ZERO,
+                position: Position::from(8),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            },
+        ]);
+
+        let effective = (0..state.state().len())
+            .map(|i| state.is_structural_counter(i))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            effective,
+            vec![false, true, true, false, true, false, false]
+        );
+    }
+
+    #[test]
+    fn honest_moves() {
+        let honest = Address::repeat_byte(0x01);
+        let mut state = mock_state();
+        state.state[1].claimant = honest;
+        state
+            .state
+            .push(alphabet_claim(2, Position::from(8), Claim::ZERO));
+        state.state[3].claimant = honest;
+
+        assert_eq!(state.honest_moves(honest), vec![1, 3]);
+        assert_eq!(state.honest_moves(Address::ZERO), vec![0, 2]);
+        assert!(state.honest_moves(Address::repeat_byte(0x02)).is_empty());
+    }
+
+    #[test]
+    fn claims_at_depth() {
+        let mut state = mock_state();
+        state.state_mut().extend([
+            ClaimData {
+                parent_index: 1,
+                visited: false,
+                value: Claim::ZERO,
+                position: Position::from(6),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            },
+            ClaimData {
+                parent_index: 2,
+                visited: false,
+                value: Claim::ZERO,
+                position: Position::from(8),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            },
+        ]);
+
+        assert_eq!(state.claims_at_depth(0), vec![0]);
+        assert_eq!(state.claims_at_depth(1), vec![1]);
+        assert_eq!(state.claims_at_depth(2), vec![2, 3]);
+        assert_eq!(state.claims_at_depth(3), vec![4]);
+        assert!(state.claims_at_depth(4).is_empty());
+
+        // A claim at the zero position is not at any depth.
+        state
+            .state_mut()
+            .push(alphabet_claim(0, Position::from(0), Claim::ZERO));
+        assert_eq!(state.claims_at_depth(0), vec![0]);
+    }
+
+    #[test]
+    fn disputed_trace_index() {
+        let mut state = mock_state();
+        state.state_mut().push(ClaimData {
+            parent_index: 2,
+            visited: false,
+            value: Claim::ZERO,
+            position: Position::from(10),
+            clock: 0,
+            claimant: Address::ZERO,
+            countered_by: Address::ZERO,
+        });
+
+        assert_eq!(state.disputed_trace_index(3).unwrap(), 2);
+        assert_eq!(
+            state.disputed_trace_index(2).unwrap_err().to_string(),
+            "Claim at depth 2 is not a leaf; max depth is 3"
+        );
+        assert_eq!(
+            state.disputed_trace_index(4).unwrap_err().to_string(),
+            "Claim does not exist"
+        );
+    }
+
+    #[test]
+    fn is_fully_bisected() {
+        let mut state = mock_state();
+        assert!(!state.is_fully_bisected(2));
+        assert!(!state.is_fully_bisected(3));
+
+        state.state_mut().extend([
+            // A leaf reached by bisecting through claim 2.
+            ClaimData {
+                parent_index: 2,
+                visited: false,
+                value: Claim::ZERO,
+                position: Position::from(10),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            },
+            // A leaf that does not counter its parent.
+            ClaimData {
+                parent_index: 0,
+                visited: false,
+                value: Claim::ZERO,
+                position: Position::from(8),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            },
+        ]);
+        assert!(state.is_fully_bisected(3));
+        assert!(!state.is_fully_bisected(4));
+    }
+
+    #[test]
+    fn next_move_toward_converges() {
+        let provider = AlphabetTraceProvider::new(b'a', 4);
+        for target in 0..16 {
+            let mut state = FaultDisputeState::new(
+                vec![alphabet_claim(u32::MAX, Position::from(1), Claim::ZERO)],
+                Claim::ZERO,
+                GameStatus::InProgress,
+                4,
+            );
+
+            // Bisect from the root until the next move is a step.
+            let mut moves = 0;
+            let (is_attack, leaf) = loop {
+                let from_claim = state.state.len() - 1;
+                match state
+                    .next_move_toward(&provider, from_claim, target)
+                    .unwrap()
+                {
+                    FaultSolverResponse::Move(is_attack, parent_index, value) => {
+                        let position = state.state[parent_index].position.make_move(is_attack);
+                        assert_eq!(value, provider.state_hash(position).unwrap());
+                        state
+                            .state
+                            .push(alphabet_claim(parent_index as u32, position, value));
+                        moves += 1;
+                    }
+                    FaultSolverResponse::Step(is_attack, claim_index, _, _) => {
+                        break (is_attack, state.state[claim_index].position);
+                    }
+                    response => panic!("unexpected response {:?}", response),
+                }
+            };
+
+            // The disputed step transitions into the target trace index.
+            assert_eq!(moves, 4);
+            assert_eq!(leaf.trace_index(4).unwrap() + !is_attack as u64, target);
+        }
+    }
+
+    #[test]
+    fn next_move_toward_deep() {
+        // Bisect at the mainnet max game depth of 73, whose trace indices near the root do not
+        // fit within a u64.
+        let max_depth = 73;
+        let provider = FnTraceProvider::new([0u8], |position: Position| {
+            Ok(Claim::from(U256::from(position.depth())))
+        })
+        .with_state_at(|_| Ok(Arc::new([0u8])));
+        for target in [0, 5, u64::MAX] {
+            let mut state = FaultDisputeState::new(
+                vec![alphabet_claim(u32::MAX, Position::from(1), Claim::ZERO)],
+                Claim::ZERO,
+                GameStatus::InProgress,
+                max_depth,
+            );
+
+            let (is_attack, leaf) = loop {
+                let from_claim = state.state.len() - 1;
+                match state
+                    .next_move_toward(&provider, from_claim, target)
+                    .unwrap()
+                {
+                    FaultSolverResponse::Move(is_attack, parent_index, value) => {
+                        let position = state.state[parent_index].position.make_move(is_attack);
+                        state
+                            .state
+                            .push(alphabet_claim(parent_index as u32, position, value));
+                    }
+                    FaultSolverResponse::Step(is_attack, claim_index, _, _) => {
+                        break (is_attack, state.state[claim_index].position);
+                    }
+                    response => panic!("unexpected response {:?}", response),
+                }
+            };
+
+            assert_eq!(state.state.len(), max_depth as usize + 1);
+            let leaf_index = leaf.trace_index(max_depth).unwrap();
+            assert_eq!(leaf_index.checked_add(!is_attack as u64), Some(target));
+        }
+    }
+
+    #[test]
+    fn next_move_toward_errors() {
+        let provider = AlphabetTraceProvider::new(b'a', 4);
+        let state = FaultDisputeState::new(
+            vec![alphabet_claim(u32::MAX, Position::from(1), Claim::ZERO)],
+            Claim::ZERO,
+            GameStatus::InProgress,
+            4,
+        );
+
+        assert_eq!(
+            state
+                .next_move_toward(&provider, 1, 0)
+                .unwrap_err()
+                .to_string(),
+            "Claim does not exist"
+        );
+        assert_eq!(
+            state
+                .next_move_toward(&provider, 0, 16)
+                .unwrap_err()
+                .to_string(),
+            "Target trace index 16 is beyond the trace"
+        );
+    }
+
+    #[test]
+    fn diff() {
+        let previous = mock_state();
+        let mut state = previous.clone();
+        state.state_mut().extend([
+            ClaimData {
+                parent_index: 1,
+                visited: false,
+                value: Claim::ZERO,
+                position: Position::from(6),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            },
+            ClaimData {
+                parent_index: 3,
+                visited: false,
+                value: Claim::ZERO,
+                position: Position::from(12),
+                clock: 0,
+                claimant: Address::ZERO,
+                countered_by: Address::ZERO,
+            },
+        ]);
+
+        assert_eq!(
+            state.diff(&previous),
+            StateDiff {
+                added: vec![3, 4],
+                countered: vec![1],
+            }
+        );
+        assert_eq!(state.diff(&state), StateDiff::default());
+    }
+
+    #[test]
+    fn needs_reload() {
+        let state = mock_state();
+        assert!(!state.needs_reload(3));
+        assert!(state.needs_reload(4));
+        assert!(state.needs_reload(2));
+    }
+
+    #[test]
+    fn to_dot() {
+        let mut state = mock_state();
+        state.state_mut()[2].value = Claim::repeat_byte(0xab);
+        // Defending the root claim does not counter it.
+        state
+            .state_mut()
+            .push(alphabet_claim(0, Position::from(3), Claim::ZERO));
+        let dot = state.to_dot().unwrap();
+
+        // Claim 2 is uncountered, so it counters claim 1, which leaves the root uncountered.
+        assert!(dot.starts_with("digraph FaultDisputeGame {\n"));
+        assert!(dot.ends_with('}'));
+        assert!(dot.contains("    0 [label=\"#0\\npos: 1\\n0x00000000..\"];\n"));
+        assert!(dot.contains("    1 [label=\"#1\\npos: 2\\n0x00000000..\", color=red];\n"));
+        assert!(dot.contains("    2 [label=\"#2\\npos: 4\\n0xabababab..\"];\n"));
+        assert!(dot.contains("    3 [label=\"#3\\npos: 3\\n0x00000000..\"];\n"));
+        assert!(dot.contains("    0 -> 1;\n"));
+        assert!(dot.contains("    1 -> 2;\n"));
+        assert!(dot.contains("    0 -> 3;\n"));
+        assert_eq!(dot.matches("->").count(), 3);
+
+        // The uncolored root agrees with the game's own resolution.
+        assert_eq!(state.resolve(true).unwrap(), GameStatus::DefenderWins);
+    }
+
+    #[test]
+    fn to_tree_view() {
+        let mut state = mock_state();
+        state.state.extend([
+            alphabet_claim(0, Position::from(3), Claim::ZERO),
+            alphabet_claim(1, Position::from(5), Claim::ZERO),
+        ]);
+        state.state[1].claimant = Address::repeat_byte(0x01);
+        state.state[4].countered_by = Address::repeat_byte(0x02);
+
+        let leaf = |position: Position, countered| TreeView {
+            position,
+            depth: position.depth(),
+            value: Claim::ZERO,
+            claimant: Address::ZERO,
+            countered,
+            children: Vec::new(),
+        };
+        let view = state.to_tree_view().unwrap();
+        assert_eq!(
+            view,
+            TreeView {
+                position: Position::from(1),
+                depth: 0,
+                value: Claim::ZERO,
+                claimant: Address::ZERO,
+                countered: false,
+                children: vec![
+                    TreeView {
+                        position: Position::from(2),
+                        depth: 1,
+                        value: Claim::ZERO,
+                        claimant: Address::repeat_byte(0x01),
+                        countered: true,
+                        children: vec![
+                            leaf(Position::from(4), false),
+                            leaf(Position::from(5), true)
+                        ],
+                    },
+                    // Defending the root claim does not counter it.
+                    leaf(Position::from(3), false),
+                ],
+            }
+        );
+
+        let json = serde_json::to_value(&view).unwrap();
+        assert_eq!(json["children"][0]["children"][1]["position"], "5");
+        assert_eq!(json["children"][0]["countered"], true);
+        assert_eq!(serde_json::from_value::<TreeView>(json).unwrap(), view);
+
+        state.state.clear();
+        assert_eq!(
+            state.to_tree_view().unwrap_err().to_string(),
+            "State DAG is empty"
+        );
+    }
+
+    /// Applies the moves to the state and resolves it as a tree of subgames, returning
+    /// whether or not the root claim is countered.
+    fn root_countered_after(
+        state: &FaultDisputeState,
+        moves: &[FaultSolverResponse<[u8; 1]>],
+    ) -> bool {
+        let mut state = state.clone();
+        let mut stepped = Vec::new();
+        for response in moves {
+            match response {
+                FaultSolverResponse::Move(is_attack, parent_index, value) => {
+                    let position = state.state[*parent_index].position.make_move(*is_attack);
+                    state
+                        .state
+                        .push(alphabet_claim(*parent_index as u32, position, *value));
+                }
+                FaultSolverResponse::Step(_, index, _, _) => stepped.push(*index),
+                _ => panic!("unexpected response"),
+            }
+        }
+
+        // Children are always appended after their parents.
+        let mut countered = vec![false; state.state.len()];
+        for i in (0..state.state.len()).rev() {
+            countered[i] |= stepped.contains(&i);
+            if state.is_structural_counter(i) && !countered[i] {
+                countered[state.state[i].parent_index as usize] = true;
+            }
+        }
+        countered[0]
+    }
+
+    #[test]
+    fn minimal_winning_moves_prunes_redundant_counters() {
+        let provider = AlphabetTraceProvider::new(b'a', 4);
+        let root_claim = provider.state_hash(Position::from(1)).unwrap();
+        let bad_claim = Claim::repeat_byte(0xFF);
+        let state = FaultDisputeState::new(
+            vec![
+                alphabet_claim(u32::MAX, Position::from(1), root_claim),
+                alphabet_claim(
+                    0,
+                    Position::from(2),
+                    provider.state_hash(Position::from(2)).unwrap(),
+                ),
+                alphabet_claim(
+                    1,
+                    Position::from(4),
+                    provider.state_hash(Position::from(4)).unwrap(),
+                ),
+                alphabet_claim(2, Position::from(8), bad_claim),
+                alphabet_claim(2, Position::from(10), bad_claim),
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+        let solver = AlphaClaimSolver::new(provider);
+
+        // Countering every claim that the solver disagrees with takes three moves.
+        let naive =
+            FaultDisputeSolver::new(AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4)))
+                .available_moves(&mut state.clone())
+                .unwrap()
+                .iter()
+                .filter(|r| !matches!(r, FaultSolverResponse::Skip(_)))
+                .count();
+        assert_eq!(naive, 3);
+
+        // Defending against claim 1 invalidates the subtree beneath it in a single move.
+        let moves = state.minimal_winning_moves(&solver, 0).unwrap();
+        assert_eq!(
+            moves,
+            vec![FaultSolverResponse::Move(
+                false,
+                1,
+                solver.provider().state_hash(Position::from(6)).unwrap()
+            )]
+        );
+        assert!(!root_countered_after(&state, &moves));
+        assert!(state.state.iter().all(|c| !c.visited));
+    }
+
+    #[test]
+    fn estimated_moves_to_win() {
+        let provider = AlphabetTraceProvider::new(b'a', 4);
+        let honest = |position| provider.state_hash(position).unwrap();
+        let bad_claim = Claim::repeat_byte(0xFF);
+        let state = FaultDisputeState::new(
+            vec![
+                alphabet_claim(u32::MAX, Position::from(1), bad_claim),
+                alphabet_claim(0, Position::from(2), honest(Position::from(2))),
+                alphabet_claim(1, Position::from(4), bad_claim),
+                alphabet_claim(1, Position::from(6), bad_claim),
+                alphabet_claim(2, Position::from(8), honest(Position::from(8))),
+                alphabet_claim(4, Position::from(16), bad_claim),
+            ],
+            bad_claim,
+            GameStatus::InProgress,
+            4,
+        );
+        let solver = AlphaClaimSolver::new(AlphabetTraceProvider::new(b'a', 4));
+
+        // The root and claim 2 are already countered by existing honest claims, leaving an
+        // attack against claim 3 and a step against claim 5.
+        assert_eq!(state.estimated_moves_to_win(&solver, 0).unwrap(), 2);
+        assert!(state.state.iter().all(|c| !c.visited));
+    }
+
+    #[test]
+    fn minimal_winning_moves_reuses_supporting_claims() {
+        let provider = AlphabetTraceProvider::new(b'a', 4);
+        let root_claim = provider.state_hash(Position::from(1)).unwrap();
+        let state = FaultDisputeState::new(
+            vec![
+                alphabet_claim(u32::MAX, Position::from(1), root_claim),
+                alphabet_claim(0, Position::from(2), Claim::repeat_byte(0xFF)),
+                alphabet_claim(
+                    1,
+                    Position::from(4),
+                    provider.state_hash(Position::from(4)).unwrap(),
+                ),
+            ],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+        let solver = AlphaClaimSolver::new(provider);
+
+        let moves = state.minimal_winning_moves(&solver, 0).unwrap();
+        assert!(moves.is_empty());
+        assert!(!root_countered_after(&state, &moves));
+    }
+
+    #[test]
+    fn minimal_winning_moves_dishonest_root() {
+        let provider = AlphabetTraceProvider::new(b'a', 4);
+        let root_claim = Claim::repeat_byte(0xFF);
+        let state = FaultDisputeState::new(
+            vec![alphabet_claim(u32::MAX, Position::from(1), root_claim)],
+            root_claim,
+            GameStatus::InProgress,
+            4,
+        );
+        let solver = AlphaClaimSolver::new(provider);
+
+        let moves = state.minimal_winning_moves(&solver, 0).unwrap();
+        assert_eq!(
+            moves,
+            vec![FaultSolverResponse::Move(
+                true,
+                0,
+                solver.provider().state_hash(Position::from(2)).unwrap()
+            )]
+        );
+        assert!(root_countered_after(&state, &moves));
+
+        // Once the clock has run out, there is no move left that wins the game.
+        let now = (state.game_duration >> 1) + 1;
+        assert_eq!(
+            state
+                .minimal_winning_moves(&solver, now)
+                .unwrap_err()
+                .to_string(),
+            "No set of moves wins the game"
+        );
+    }
+
+    #[test]
+    fn claim_data_serde_round_trip() {
+        let claims = vec![
+            ClaimData {
+                parent_index: u32::MAX,
+                visited: false,
+                value: Claim::repeat_byte(0xAB),
+                position: Position::from(1),
+                clock: 0,
+                claimant: Address::repeat_byte(0x01),
+                countered_by: Address::ZERO,
+            },
+            ClaimData {
+                parent_index: 0,
+                visited: true,
+                value: Claim::repeat_byte(0xCD),
+                position: Position::from(u128::MAX),
+                clock: (1 << 64) | 1_700_000_000,
+                claimant: Address::repeat_byte(0x02),
+                countered_by: Address::repeat_byte(0x03),
+            },
+        ];
+
+        let json = serde_json::to_value(&claims).unwrap();
+        assert_eq!(json[0]["parent_index"], u32::MAX);
+        assert_eq!(json[0]["value"], format!("0x{}", "ab".repeat(32)));
+        assert_eq!(json[0]["claimant"], format!("0x{}", "01".repeat(20)));
+        assert_eq!(json[1]["position"], u128::MAX.to_string());
+        assert_eq!(json[1]["clock"], "18446744075409551616");
+        assert_eq!(
+            serde_json::from_value::<Vec<ClaimData>>(json).unwrap(),
+            claims
+        );
+    }
+
+    #[test]
+    fn subgame_outcomes() {
+        let mut state = FaultDisputeState::new(
+            vec![
+                alphabet_claim(u32::MAX, Position::from(1), Claim::ZERO),
+                alphabet_claim(0, Position::from(2), Claim::ZERO),
+                alphabet_claim(1, Position::from(4), Claim::ZERO),
+                alphabet_claim(1, Position::from(6), Claim::ZERO),
+                alphabet_claim(2, Position::from(8), Claim::ZERO),
+                // Defending the root claim does not counter it.
+                alphabet_claim(0, Position::from(3), Claim::ZERO),
+            ],
+            Claim::ZERO,
+            GameStatus::InProgress,
+            4,
+        );
+        let expired = (state.game_duration >> 1) + 100;
+
+        // Resolved by hand: claim 4 counters claim 2, and claim 3 counters claim 1, leaving
+        // the root claim uncountered.
+        assert_eq!(
+            state.subgame_outcomes(expired).unwrap(),
+            vec![
+                (0, false),
+                (1, true),
+                (2, true),
+                (3, false),
+                (4, false),
+                (5, false)
+            ]
+        );
+
+        // A claim that was already countered by a step stays countered, uncountering its
+        // parent. Claim 1 remains countered by claim 3.
+        state.state[4].countered_by = Address::repeat_byte(0x01);
+        assert_eq!(
+            state.subgame_outcomes(expired).unwrap(),
+            vec![
+                (0, false),
+                (1, true),
+                (2, false),
+                (3, false),
+                (4, true),
+                (5, false)
+            ]
+        );
+
+        // Subgames above a claim whose clock has not expired cannot be resolved yet.
+        state.state[4].clock = 200;
+        assert_eq!(
+            state.subgame_outcomes(expired).unwrap(),
+            vec![(3, false), (5, false)]
+        );
+    }
+
+    #[test]
+    fn resolvable_subgames() {
+        let mut state = FaultDisputeState::new(
+            vec![
+                alphabet_claim(u32::MAX, Position::from(1), Claim::ZERO),
+                alphabet_claim(0, Position::from(2), Claim::ZERO),
+                alphabet_claim(1, Position::from(4), Claim::ZERO),
+                alphabet_claim(1, Position::from(6), Claim::ZERO),
+                alphabet_claim(2, Position::from(8), Claim::ZERO),
+                // Defending the root claim does not counter it.
+                alphabet_claim(0, Position::from(3), Claim::ZERO),
+            ],
+            Claim::ZERO,
+            GameStatus::InProgress,
+            4,
+        );
+        state
+            .state
+            .iter_mut()
+            .enumerate()
+            .for_each(|(i, c)| c.claimant = Address::with_last_byte(i as u8 + 1));
+        let expired = (state.game_duration >> 1) + 100;
+
+        // Each subgame that can be resolved next agrees with resolving it on a copy of the
+        // state, until the whole DAG has been resolved bottom-up.
+        let mut expected = vec![(3, false), (4, false), (5, false)];
+        while !expected.is_empty() {
+            assert_eq!(state.resolvable_subgames(expired), expected);
+            for (index, countered) in &expected {
+                assert_eq!(
+                    state.clone().resolve_subgame(*index, expired).unwrap(),
+                    *countered
+                );
+            }
+            for (index, _) in &expected {
+                state.resolve_subgame(*index, expired).unwrap();
+            }
+            expected = (0..state.state.len())
+                .filter(|i| state.clone().resolve_subgame(*i, expired).is_ok())
+                .map(|i| (i, state.clone().resolve_subgame(i, expired).unwrap()))
+                .collect();
+        }
+        assert_eq!(state.resolved_subgames.len(), 6);
+        assert_eq!(state.state[1].countered_by, Address::with_last_byte(4));
+        assert!(state.state[0].countered_by.is_zero());
+
+        // Subgames whose clocks have not expired cannot be resolved.
+        assert!(state.resolvable_subgames(0).is_empty());
+    }
+
+    #[test]
+    fn step_bundle() {
+        let provider = FixtureTraceProvider::from_file(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/testdata/alphabet_trace.json"
+        ))
+        .unwrap();
+        let honest = |position| provider.state_hash(position).unwrap();
+        let state = FaultDisputeState::new(
+            vec![
+                alphabet_claim(u32::MAX, Position::from(1), Claim::ZERO),
+                alphabet_claim(0, Position::from(2), honest(Position::from(2))),
+                alphabet_claim(1, Position::from(4), Claim::ZERO),
+                alphabet_claim(2, Position::from(8), honest(Position::from(8))),
+                alphabet_claim(3, Position::from(16), Claim::ZERO),
+                alphabet_claim(3, Position::from(18), Claim::ZERO),
+            ],
+            Claim::ZERO,
+            GameStatus::InProgress,
+            4,
+        );
+
+        // Attacking the first leaf steps from the absolute prestate to the leaf itself.
+        let bundle = state.step_bundle(4, true, &provider).unwrap();
+        assert_eq!(bundle.prestate_pos, None);
+        assert_eq!(bundle.prestate_data, provider.absolute_prestate());
+        assert!(bundle.prestate_proof.is_empty());
+        assert_eq!(
+            (bundle.post_claim, bundle.post_claim_pos),
+            (Claim::ZERO, Position::from(16))
+        );
+
+        // Defending the first leaf steps from the leaf to the ancestor at trace index 1.
+        let bundle = state.step_bundle(4, false, &provider).unwrap();
+        assert_eq!(bundle.prestate_pos, Some(Position::from(16)));
+        assert_eq!(bundle.prestate_data.as_ref().as_ref(), b"b");
+        assert_eq!(
+            (bundle.post_claim, bundle.post_claim_pos),
+            (honest(Position::from(8)), Position::from(8))
+        );
+
+        let bundle = state.step_bundle(5, true, &provider).unwrap();
+        assert_eq!(bundle.prestate_pos, Some(Position::from(17)));
+        assert_eq!(bundle.prestate_data.as_ref().as_ref(), b"c");
+        assert_eq!(
+            (bundle.post_claim, bundle.post_claim_pos),
+            (Claim::ZERO, Position::from(18))
+        );
+
+        let bundle = state.step_bundle(5, false, &provider).unwrap();
+        assert_eq!(bundle.prestate_pos, Some(Position::from(18)));
+        assert_eq!(bundle.prestate_data.as_ref().as_ref(), b"d");
+        assert_eq!(
+            (bundle.post_claim, bundle.post_claim_pos),
+            (Claim::ZERO, Position::from(4))
+        );
+
+        assert_eq!(
+            state
+                .step_bundle(3, true, &provider)
+                .unwrap_err()
+                .to_string(),
+            "Claim 3 is not at the max depth"
+        );
+    }
+
+    #[test]
+    fn validate_zero_position() {
+        let mut state = mock_state();
+        state.state[2].position = Position::from(0);
+        assert_eq!(
+            state.validate(true).unwrap_err().to_string(),
+            "Claim 2 has invalid position 0"
+        );
+
+        state.state[0].position = Position::from(0);
+        assert_eq!(
+            state.assert_root_invariants().unwrap_err().to_string(),
+            "Root claim has invalid position 0"
+        );
+    }
+
+    #[test]
+    fn validate_self_countered() {
+        let mut state = mock_state();
+        state.state[2].claimant = Address::repeat_byte(0x01);
+        state.state[2].countered_by = Address::repeat_byte(0x02);
+        state.validate(false).unwrap();
+
+        state.state[2].countered_by = Address::repeat_byte(0x01);
+        assert_eq!(
+            state.validate(false).unwrap_err().to_string(),
+            format!(
+                "Claim 2 was countered by its own claimant {}",
+                Address::repeat_byte(0x01)
+            )
+        );
+    }
+
+    #[test]
+    fn validate_status_bytes() {
+        let with_status = |status: VMStatus| {
+            let mut claim = Claim::repeat_byte(0x42);
+            claim[0] = status as u8;
+            claim
+        };
+        let mut state = FaultDisputeState::new(
+            vec![
+                alphabet_claim(u32::MAX, Position::from(1), with_status(VMStatus::Invalid)),
+                alphabet_claim(0, Position::from(2), with_status(VMStatus::Unfinished)),
+                alphabet_claim(1, Position::from(4), with_status(VMStatus::Unfinished)),
+                alphabet_claim(2, Position::from(8), with_status(VMStatus::Unfinished)),
+                alphabet_claim(3, Position::from(16), with_status(VMStatus::Invalid)),
+            ],
+            with_status(VMStatus::Invalid),
+            GameStatus::InProgress,
+            4,
+        );
+        // The root claim and leaf claims may carry any status.
+        state.validate(true).unwrap();
+
+        state.state[2].value = with_status(VMStatus::Invalid);
+        state.validate(false).unwrap();
+        assert_eq!(
+            state.validate(true).unwrap_err().to_string(),
+            "Claim 2 at depth 2 carries VM status 1"
+        );
+
+        // Structural checks apply regardless of strictness.
+        state.state[4].parent_index = 5;
+        assert_eq!(
+            state.validate(false).unwrap_err().to_string(),
+            "Claim 4 references missing parent 5"
+        );
+    }
+}
@@ -1,6 +1,8 @@
 //! This module holds traits related to the [FaultDisputeGame]
 
-use crate::{state::ClaimData, FaultDisputeState, FaultSolverResponse, Position};
+use crate::{
+    checked_gindex, state::ClaimData, AnchorInfo, FaultDisputeState, FaultSolverResponse, Position,
+};
 use durin_primitives::{Claim, DisputeGame};
 use std::sync::Arc;
 
@@ -15,10 +17,55 @@ pub trait FaultDisputeGame: DisputeGame {
     fn state_mut(&mut self) -> &mut Vec<ClaimData>;
 }
 
+/// A [ClaimDag] is a read-only view over the claims of a [FaultDisputeGame]'s state DAG,
+/// independent of how the claims are stored.
+pub trait ClaimDag {
+    /// Returns the number of claims within the DAG.
+    fn len(&self) -> usize;
+
+    /// Returns whether or not the DAG holds no claims.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the claim at the given index, or [None] if it does not exist.
+    fn claim(&self, index: usize) -> Option<ClaimData>;
+
+    /// Returns the max depth of the position tree.
+    fn max_depth(&self) -> u8;
+
+    /// Checks if the claim at the given index structurally counters its parent. A claim is a
+    /// structural counter if its position is the result of an attack or defense move against
+    /// its parent's position, it does not defend the root claim, and it is within the bounds
+    /// of the position tree. Claims that do not counter their parent cannot affect the
+    /// outcome of the game and do not need to be countered.
+    ///
+    /// The check is purely structural, as it is in the contract's resolution: the values of
+    /// the claims and any local opinion of them are not taken into account. Whether a claim
+    /// agrees with the local trace is decided by the [FaultClaimSolver].
+    ///
+    /// The root claim does not counter any claim, so it is never a structural counter.
+    fn is_structural_counter(&self, index: usize) -> bool {
+        let Some(claim) = self.claim(index) else {
+            return false;
+        };
+        let Some(parent) = self.claim(claim.parent_index as usize) else {
+            return false;
+        };
+
+        let is_attack = claim.position == parent.position.make_move(true);
+        let is_defense =
+            claim.position == parent.position.make_move(false) && parent.parent_index != u32::MAX;
+
+        (is_attack || is_defense) && claim.position.depth() <= self.max_depth()
+    }
+}
+
 /// A [FaultClaimSolver] is a solver that finds the correct response to a given [durin_primitives::Claim]
 /// within a [FaultDisputeGame].
 pub trait FaultClaimSolver<T: AsRef<[u8]>, P: TraceProvider<T>> {
-    /// Finds the best move against a [crate::ClaimData] in a given [FaultDisputeState].
+    /// Finds the best move against a [crate::ClaimData] in a given [FaultDisputeState]. The
+    /// state is not mutated, and tracking which claims have been solved is left to the caller.
     ///
     /// ### Takes
     /// - `world`: The [FaultDisputeState] to solve against.
@@ -29,7 +76,7 @@ pub trait FaultClaimSolver<T: AsRef<[u8]>, P: TraceProvider<T>> {
     /// - [FaultSolverResponse] or [Err]: The best move against the claim.
     fn solve_claim(
         &self,
-        world: &mut FaultDisputeState,
+        world: &FaultDisputeState,
         claim_index: usize,
         attacking_root: bool,
     ) -> anyhow::Result<FaultSolverResponse<T>>;
@@ -37,6 +84,12 @@ pub trait FaultClaimSolver<T: AsRef<[u8]>, P: TraceProvider<T>> {
     /// Returns a shared reference to the [TraceProvider] that the solver uses to fetch
     /// the state of the VM and commitments to it.
     fn provider(&self) -> &P;
+
+    /// Returns the local opinion of the state hash at the given position. By default, this is
+    /// the state hash served by the solver's [TraceProvider].
+    fn state_hash(&self, position: Position) -> anyhow::Result<Claim> {
+        self.provider().state_hash(position)
+    }
 }
 
 /// A [TraceProvider] is a type that can provide the raw state (in bytes) at a given
@@ -54,18 +107,54 @@ pub trait TraceProvider<P: AsRef<[u8]>> {
     /// Returns the state hash at the given position.
     fn state_hash(&self, position: Position) -> anyhow::Result<Claim>;
 
+    /// Returns the state hash at the position with the given depth and index at depth.
+    fn state_hash_at(&self, depth: u8, index_at_depth: u64) -> anyhow::Result<Claim> {
+        self.state_hash(checked_gindex(depth, index_at_depth)?)
+    }
+
     /// Returns the raw proof for the commitment at the given position.
     fn proof_at(&self, position: Position) -> anyhow::Result<Arc<[u8]>>;
+
+    /// Returns the max depth of the position tree that the provider computes trace indices
+    /// against, or [None] if the provider does not advertise one. By default, no depth is
+    /// advertised.
+    fn max_depth(&self) -> Option<u8> {
+        None
+    }
+
+    /// Returns the starting anchor of the provider's trace. By default, the anchor is the
+    /// absolute prestate hash, and the trace is not anchored to a block.
+    fn anchor(&self) -> AnchorInfo {
+        AnchorInfo {
+            block_number: None,
+            prestate_hash: self.absolute_prestate_hash(),
+        }
+    }
+
+    /// Overrides the absolute prestate hash served by the provider, without recomputing it
+    /// from the raw absolute prestate. By default, overriding is unsupported and returns an
+    /// error.
+    fn override_absolute_prestate(&mut self, _hash: Claim) -> anyhow::Result<()> {
+        anyhow::bail!("Overriding the absolute prestate is not supported by this provider")
+    }
 }
 
 /// The [Gindex] trait defines the interface of a generalized index within a binary tree.
 /// A "Generalized Index" is calculated as `2^{depth} + index_at_depth`.
 pub trait Gindex {
-    /// Returns the depth of the [Position] within the tree.
+    /// Returns whether or not the [Position] is within the tree. Generalized indices start at
+    /// 1 for the root, so the zero position is not part of the tree and has no depth. Loaded
+    /// positions should be checked before any other method is called on them.
+    fn is_valid(&self) -> bool;
+
+    /// Returns the depth of the [Position] within the tree. An invalid position has no depth,
+    /// and is reported at depth 0, so [Gindex::is_valid] must be used to tell it apart from
+    /// the root.
     fn depth(&self) -> u8;
 
-    /// Returns the index at depth of the [Position] within the tree.
-    fn index_at_depth(&self) -> u64;
+    /// Returns the index at depth of the [Position] within the tree, or an error if the index
+    /// does not fit within a [u64].
+    fn index_at_depth(&self) -> anyhow::Result<u64>;
 
     /// Returns the left child [Position] relative to the current [Position].
     fn left(&self) -> Self;
@@ -79,8 +168,9 @@ pub trait Gindex {
     /// Returns the rightmost [Position] that commits to the same trace index as the current [Position].
     fn right_index(&self, max_depth: u8) -> Self;
 
-    /// Returns the trace index that the current [Position] commits to.
-    fn trace_index(&self, max_depth: u8) -> u64;
+    /// Returns the trace index that the current [Position] commits to, or an error if the
+    /// trace index does not fit within a [u64].
+    fn trace_index(&self, max_depth: u8) -> anyhow::Result<u64>;
 
     /// Returns the relative [Position] for an attack or defense move against the current [Position].
     fn make_move(&self, is_attack: bool) -> Self;
@@ -95,4 +185,62 @@ pub trait ChessClock {
 
     /// Returns the timestamp of when the chess clock was last stopped.
     fn timestamp(&self) -> u64;
+
+    /// Returns whether or not the chess clock is paused at the given timestamp. Clocks are
+    /// paused for the duration of the freeze period.
+    ///
+    /// ### Takes
+    /// - `now`: The current timestamp, in seconds.
+    /// - `freeze_start`: The timestamp at which the freeze period starts.
+    /// - `freeze_duration`: The duration of the freeze period, in seconds.
+    fn is_paused(&self, now: u64, freeze_start: u64, freeze_duration: u64) -> bool {
+        now >= freeze_start && now - freeze_start < freeze_duration
+    }
+
+    /// Returns the seconds elapsed on the chess clock at the given timestamp, assuming that it
+    /// has been running since it was last stopped. Time spent within the freeze period is not
+    /// counted. The duration saturates at [u64::MAX].
+    ///
+    /// ### Takes
+    /// - `now`: The current timestamp, in seconds.
+    /// - `freeze_start`: The timestamp at which the freeze period starts.
+    /// - `freeze_duration`: The duration of the freeze period, in seconds.
+    fn duration_at(&self, now: u64, freeze_start: u64, freeze_duration: u64) -> u64 {
+        let elapsed = now.saturating_sub(self.timestamp());
+        let paused = now
+            .min(freeze_start.saturating_add(freeze_duration))
+            .saturating_sub(self.timestamp().max(freeze_start));
+        self.duration()
+            .saturating_add(elapsed.saturating_sub(paused))
+    }
+
+    /// Returns the seconds left on the running chess clock at the given timestamp. See
+    /// [ChessClock::duration_at].
+    ///
+    /// ### Takes
+    /// - `now`: The current timestamp, in seconds.
+    /// - `max_duration`: The seconds allotted to the chess clock.
+    /// - `freeze_start`: The timestamp at which the freeze period starts.
+    /// - `freeze_duration`: The duration of the freeze period, in seconds.
+    fn time_left(
+        &self,
+        now: u64,
+        max_duration: u64,
+        freeze_start: u64,
+        freeze_duration: u64,
+    ) -> u64 {
+        max_duration.saturating_sub(self.duration_at(now, freeze_start, freeze_duration))
+    }
+
+    /// Returns whether or not the running chess clock has run out of time at the given
+    /// timestamp. See [ChessClock::time_left].
+    fn is_expired(
+        &self,
+        now: u64,
+        max_duration: u64,
+        freeze_start: u64,
+        freeze_duration: u64,
+    ) -> bool {
+        self.time_left(now, max_duration, freeze_start, freeze_duration) == 0
+    }
 }
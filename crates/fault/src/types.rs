@@ -2,12 +2,111 @@
 
 use crate::ChessClock;
 use crate::Gindex;
+use alloy_primitives::Bytes;
+#[cfg(feature = "wide-position")]
+use alloy_primitives::U256;
 use durin_primitives::Claim;
-use std::sync::Arc;
+use serde::{Deserialize, Serialize};
+use std::{
+    convert::TryFrom,
+    fmt, mem,
+    ops::{Add, Deref, RangeInclusive, Sub},
+    str::FromStr,
+    sync::Arc,
+};
+
+/// The integer type backing a [Position]. With the `wide-position` feature, positions are
+/// backed by a [U256], allowing for position trees deeper than 127 levels.
+#[cfg(not(feature = "wide-position"))]
+pub type PositionValue = u128;
+
+/// The integer type backing a [Position]. With the `wide-position` feature, positions are
+/// backed by a [U256], allowing for position trees deeper than 127 levels.
+#[cfg(feature = "wide-position")]
+pub type PositionValue = U256;
+
+/// A [Position] is a generalized index within the position tree of a
+/// [crate::FaultDisputeGame]. It is a distinct type from [Clock], so that the two cannot be
+/// passed in place of each other.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Position(pub PositionValue);
 
-pub type Position = u128;
 pub type Clock = u128;
 
+impl From<u128> for Position {
+    fn from(position: u128) -> Self {
+        Self(PositionValue::from(position))
+    }
+}
+
+impl From<Position> for PositionValue {
+    fn from(position: Position) -> Self {
+        position.0
+    }
+}
+
+impl FromStr for Position {
+    type Err = <PositionValue as FromStr>::Err;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.parse::<PositionValue>().map(Self)
+    }
+}
+
+impl Deref for Position {
+    type Target = PositionValue;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl fmt::Display for Position {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl Add<u128> for Position {
+    type Output = Self;
+
+    fn add(self, rhs: u128) -> Self::Output {
+        Self(self.0 + PositionValue::from(rhs))
+    }
+}
+
+impl Sub<u128> for Position {
+    type Output = Self;
+
+    fn sub(self, rhs: u128) -> Self::Output {
+        Self(self.0 - PositionValue::from(rhs))
+    }
+}
+
+impl Position {
+    /// The position of the root claim.
+    #[cfg(not(feature = "wide-position"))]
+    pub const ROOT: Self = Self(1);
+
+    /// The position of the root claim.
+    #[cfg(feature = "wide-position")]
+    pub const ROOT: Self = Self(U256::from_limbs([1, 0, 0, 0]));
+
+    /// Returns the range of trace indices committed to by the leaves beneath the [Position].
+    /// A leaf commits to a single trace index, while the root commits to the entire trace.
+    ///
+    /// ### Takes
+    /// - `max_depth`: The max depth of the position tree.
+    ///
+    /// ### Returns
+    /// - `RangeInclusive<u64>` or [Err]: The trace indices of the leftmost and rightmost leaves
+    ///   beneath the [Position], or an error if either does not fit within a [u64].
+    pub fn trace_range(&self, max_depth: u8) -> anyhow::Result<RangeInclusive<u64>> {
+        let leftmost = Self(self.0 << (max_depth - self.depth()) as usize);
+        Ok(leftmost.index_at_depth()?..=self.trace_index(max_depth)?)
+    }
+}
+
 /// The [FaultSolverResponse] enum describes the response that a solver should
 /// return when asked to make a move.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -19,6 +118,151 @@ pub enum FaultSolverResponse<T: AsRef<[u8]>> {
     /// A response indicating that the proper move is to perform a VM step against
     /// the given claim.
     Step(bool, usize, Arc<T>, Arc<[u8]>),
+    /// A response indicating that the proper move against the given claim could not be
+    /// determined because the trace provider was unavailable, or because the claim's parent
+    /// has not been loaded yet. The claim should be solved again at a later time.
+    Defer(usize),
+    /// A response indicating that the subgame rooted at the given claim can be resolved in
+    /// favor of the local opinion, and that the proper action is to resolve it.
+    Resolve(usize),
+}
+
+impl<T: AsRef<[u8]>> FaultSolverResponse<T> {
+    /// Returns the index of the claim within the state DAG that the response is for.
+    pub fn claim_index(&self) -> usize {
+        match self {
+            Self::Move(_, claim_index, _)
+            | Self::Skip(claim_index)
+            | Self::Step(_, claim_index, _, _)
+            | Self::Defer(claim_index)
+            | Self::Resolve(claim_index) => *claim_index,
+        }
+    }
+}
+
+/// The [ProviderError] wraps an error returned by a [crate::TraceProvider] while solving a
+/// claim, so that a failure to fetch the local opinion can be told apart from an error in the
+/// state being solved. It displays as the wrapped error.
+#[derive(Debug)]
+pub struct ProviderError(pub anyhow::Error);
+
+impl fmt::Display for ProviderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.fmt(f)
+    }
+}
+
+impl std::error::Error for ProviderError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.0.source()
+    }
+}
+
+/// The [MoveReason] enum describes why a solver chose a [FaultSolverResponse].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MoveReason {
+    /// The claim supports the local opinion of the root claim, so it is not countered.
+    SupportsLocalOpinion,
+    /// The claim does not counter its parent and cannot affect the outcome of the game.
+    IneffectiveCounter,
+    /// The local opinion disagrees with the claim, so it is attacked.
+    Disagrees,
+    /// The local opinion agrees with the claim, so it is defended.
+    Agrees,
+    /// The trace provider was unavailable, so the claim was deferred.
+    ProviderUnavailable,
+    /// The claim's subgame can be resolved in favor of the local opinion.
+    Resolvable,
+}
+
+/// The [MoveReport] struct pairs a [FaultSolverResponse] with the reasoning behind it, for
+/// diagnosing the choices made by a solver.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoveReport<T: AsRef<[u8]>> {
+    /// The response chosen by the solver.
+    pub response: FaultSolverResponse<T>,
+    /// The value of the opponent's claim that the response was computed against.
+    pub observed_claim: Claim,
+    /// The local opinion of the state hash at the claim's position, if the trace provider
+    /// could serve it.
+    pub honest_claim: Option<Claim>,
+    /// The reason the response was chosen.
+    pub reason: MoveReason,
+}
+
+/// The [DeadlineMoves] struct holds the responses computed by a solving pass that was cut
+/// short by a deadline, along with the claims that were not solved in time.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeadlineMoves<T: AsRef<[u8]>> {
+    /// The responses for the claims that were solved before the deadline, ordered by claim
+    /// index.
+    pub responses: Vec<FaultSolverResponse<T>>,
+    /// The indices of the claims that were not solved before the deadline. These claims are
+    /// left unvisited, so that they are solved again in the next pass.
+    pub timed_out: Vec<usize>,
+}
+
+/// The [AuditedResponse] enum is a serializable copy of a [FaultSolverResponse], recorded in
+/// a [DecisionRecord].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AuditedResponse {
+    /// A move, posting the given claim.
+    Move { claim: Claim },
+    /// A skip.
+    Skip,
+    /// A VM step, with the given state data and proof.
+    Step { state_data: Bytes, proof: Bytes },
+    /// A deferral.
+    Defer,
+    /// A resolution of the claim's subgame.
+    Resolve,
+}
+
+impl<T: AsRef<[u8]>> From<&FaultSolverResponse<T>> for AuditedResponse {
+    fn from(response: &FaultSolverResponse<T>) -> Self {
+        match response {
+            FaultSolverResponse::Move(_, _, claim) => Self::Move { claim: *claim },
+            FaultSolverResponse::Skip(_) => Self::Skip,
+            FaultSolverResponse::Step(_, _, state_data, proof) => Self::Step {
+                state_data: Bytes::copy_from_slice(state_data.as_ref().as_ref()),
+                proof: Bytes::copy_from_slice(proof),
+            },
+            FaultSolverResponse::Defer(_) => Self::Defer,
+            FaultSolverResponse::Resolve(_) => Self::Resolve,
+        }
+    }
+}
+
+/// The [DecisionRecord] struct is a serializable audit record of a single decision made by a
+/// solver, holding everything needed to verify the decision after the fact.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DecisionRecord {
+    /// The index of the claim within the state DAG that the decision was made against.
+    pub claim_index: usize,
+    /// The local opinion of the state hash at the claim's position, if the trace provider
+    /// could serve it.
+    pub honest_claim: Option<Claim>,
+    /// The value of the opponent's claim.
+    pub opponent_claim: Claim,
+    /// The depth of the claim within the position tree.
+    pub depth: u8,
+    /// Whether the response attacks the claim, or [None] if the response is not a move or a
+    /// step.
+    pub is_attack: Option<bool>,
+    /// The response chosen by the solver.
+    pub response: AuditedResponse,
+}
+
+/// The [AnchorInfo] struct describes the starting anchor that a [crate::TraceProvider]'s
+/// trace is computed from, for sanity checks and display.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnchorInfo {
+    /// The block number that the trace starts from, if the provider's trace is anchored to
+    /// a block.
+    pub block_number: Option<u64>,
+    /// The hash of the absolute prestate that the trace starts from.
+    pub prestate_hash: Claim,
 }
 
 /// The [VMStatus] enum describes the status of a VM at a given position.
@@ -34,6 +278,63 @@ pub enum VMStatus {
     Unfinished = 3,
 }
 
+/// The [BisectionConvention] enum describes how the attack and defense moves of a game's
+/// contract map onto the position tree.
+/// - [BisectionConvention::OpStack]: The canonical convention of [Gindex::make_move], used by
+///   the OP Stack's `FaultDisputeGame`.
+/// - [BisectionConvention::Inverted]: The opposite convention used by some third-party games,
+///   where an attack moves to the canonical defense position and vice versa.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BisectionConvention {
+    #[default]
+    OpStack,
+    Inverted,
+}
+
+impl BisectionConvention {
+    /// Maps the direction of a move between the canonical convention and this convention.
+    /// The mapping is its own inverse.
+    pub fn orient(&self, is_attack: bool) -> bool {
+        is_attack ^ (*self == Self::Inverted)
+    }
+
+    /// Returns the position of a move against the given position, where `is_attack` is the
+    /// direction of the move in this convention.
+    pub fn make_move<G: Gindex>(&self, position: &G, is_attack: bool) -> G {
+        position.make_move(self.orient(is_attack))
+    }
+
+    /// Returns the position of the pre-state of a step against the leaf at the given position,
+    /// where `is_attack` is the direction of the step in this convention. The pre-state is
+    /// left of the leaf when attacking in the canonical convention, or at the leaf when
+    /// defending.
+    ///
+    /// ### Returns
+    /// - `Option<Position>`: The position of the pre-state, or [None] if the pre-state is the
+    ///   absolute prestate.
+    pub fn step_prestate_position(&self, position: Position, is_attack: bool) -> Option<Position> {
+        let is_attack = self.orient(is_attack);
+        if matches!(position.index_at_depth(), Ok(0)) && is_attack {
+            return None;
+        }
+        Some(position - is_attack as u128)
+    }
+}
+
+/// Compares two claims, ignoring the [VMStatus] byte stamped into the first byte of each.
+///
+/// The status byte is only meaningful where a claim commits to a state that the VM status
+/// is checked against:
+/// - The root claim, which commits to the final status of the trace.
+/// - Claims at the max depth of the position tree, which commit to the states that a
+///   step is executed against.
+///
+/// Claims between the root and the max depth only bisect the trace, so their status bytes
+/// should not be compared.
+pub fn claims_equal_ignoring_status(a: &Claim, b: &Claim) -> bool {
+    a[1..] == b[1..]
+}
+
 /// Computes a generalized index from a depth and index at depth.
 ///
 /// ### Takes
@@ -41,19 +342,70 @@ pub enum VMStatus {
 /// - `index_at_depth`: The index at depth of the generalized index.
 ///
 /// ### Returns
-/// - `u128`: The generalized index: `2^{depth} + index_at_depth`.
-pub fn compute_gindex(depth: u8, index_at_depth: u64) -> u128 {
-    2u128.pow(depth as u32) + index_at_depth as u128
+/// - [Position]: The generalized index: `2^{depth} + index_at_depth`.
+pub fn compute_gindex(depth: u8, index_at_depth: u64) -> Position {
+    Position((PositionValue::from(1u8) << depth as usize) + PositionValue::from(index_at_depth))
 }
 
-/// Implementation of the [Gindex] trait for the [Position] type alias.
-impl Gindex for Position {
+/// Returns the positions that an honest proposer is expected to occupy along its defense
+/// line: the root and every even depth along the leftmost path of the position tree.
+///
+/// This is a heuristic rather than a prediction from a trace. It assumes a challenger that
+/// attacks each of the proposer's claims with a faulty claim, which the proposer in turn
+/// attacks, and so does not depend on the values of any trace. A challenger that agrees with
+/// part of the trace leads the proposer off this path.
+///
+/// ### Takes
+/// - `max_depth`: The max depth of the position tree.
+///
+/// ### Returns
+/// - The positions of the proposer's claims along the defense line, ordered by depth.
+pub fn defense_line(max_depth: u8) -> Vec<Position> {
+    (0..=max_depth)
+        .step_by(2)
+        .map(|depth| compute_gindex(depth, 0))
+        .collect()
+}
+
+/// Computes a generalized index from a depth and index at depth, checking that the index is
+/// within the bounds of the depth. See [compute_gindex].
+///
+/// ### Takes
+/// - `depth`: The depth of the generalized index.
+/// - `index_at_depth`: The index at depth of the generalized index.
+///
+/// ### Returns
+/// - [Position] or [Err]: The generalized index, or an error if the depth is too deep for a
+///   [Position] or the index is out of bounds at the depth.
+pub fn checked_gindex(depth: u8, index_at_depth: u64) -> anyhow::Result<Position> {
+    if depth as usize >= mem::size_of::<PositionValue>() * 8 {
+        anyhow::bail!("Depth {} is too deep for a position", depth);
+    }
+    if depth < u64::BITS as u8 && index_at_depth >> depth != 0 {
+        anyhow::bail!(
+            "Index {} is out of bounds at depth {}",
+            index_at_depth,
+            depth
+        );
+    }
+    Ok(compute_gindex(depth, index_at_depth))
+}
+
+/// Implementation of the [Gindex] trait for raw `u128` generalized indices, which back
+/// [Position]s unless the `wide-position` feature is enabled.
+impl Gindex for u128 {
+    fn is_valid(&self) -> bool {
+        *self != 0
+    }
+
     fn depth(&self) -> u8 {
-        127 - self.leading_zeros() as u8
+        (u128::BITS - 1).saturating_sub(self.leading_zeros()) as u8
     }
 
-    fn index_at_depth(&self) -> u64 {
-        (self - (1 << self.depth())) as u64
+    fn index_at_depth(&self) -> anyhow::Result<u64> {
+        u64::try_from(self.saturating_sub(1 << self.depth())).map_err(|_| {
+            anyhow::anyhow!("Index at depth of position {} does not fit in a u64", self)
+        })
     }
 
     fn left(&self) -> Self {
@@ -73,8 +425,14 @@ impl Gindex for Position {
         (self << remaining) | ((1 << remaining) - 1)
     }
 
-    fn trace_index(&self, max_depth: u8) -> u64 {
-        self.right_index(max_depth).index_at_depth()
+    fn trace_index(&self, max_depth: u8) -> anyhow::Result<u64> {
+        self.right_index(max_depth).index_at_depth().map_err(|_| {
+            anyhow::anyhow!(
+                "Trace index of position {} at max depth {} does not fit in a u64",
+                self,
+                max_depth
+            )
+        })
     }
 
     fn make_move(&self, is_attack: bool) -> Self {
@@ -82,6 +440,96 @@ impl Gindex for Position {
     }
 }
 
+/// Implementation of the [Gindex] trait for the [Position] type, delegating to the raw
+/// [PositionValue] generalized index.
+impl Gindex for Position {
+    fn is_valid(&self) -> bool {
+        self.0.is_valid()
+    }
+
+    fn depth(&self) -> u8 {
+        self.0.depth()
+    }
+
+    fn index_at_depth(&self) -> anyhow::Result<u64> {
+        self.0.index_at_depth()
+    }
+
+    fn left(&self) -> Self {
+        Self(self.0.left())
+    }
+
+    fn right(&self) -> Self {
+        Self(self.0.right())
+    }
+
+    fn parent(&self) -> Self {
+        Self(self.0.parent())
+    }
+
+    fn right_index(&self, max_depth: u8) -> Self {
+        Self(self.0.right_index(max_depth))
+    }
+
+    fn trace_index(&self, max_depth: u8) -> anyhow::Result<u64> {
+        self.0.trace_index(max_depth)
+    }
+
+    fn make_move(&self, is_attack: bool) -> Self {
+        Self(self.0.make_move(is_attack))
+    }
+}
+
+/// Implementation of the [Gindex] trait for raw [U256] generalized indices, which back
+/// [Position]s with the `wide-position` feature.
+#[cfg(feature = "wide-position")]
+impl Gindex for U256 {
+    fn is_valid(&self) -> bool {
+        *self != U256::ZERO
+    }
+
+    fn depth(&self) -> u8 {
+        255usize.saturating_sub(self.leading_zeros()) as u8
+    }
+
+    fn index_at_depth(&self) -> anyhow::Result<u64> {
+        u64::try_from(self.saturating_sub(U256::from(1) << self.depth() as usize)).map_err(|_| {
+            anyhow::anyhow!("Index at depth of position {} does not fit in a u64", self)
+        })
+    }
+
+    fn left(&self) -> Self {
+        self << 1
+    }
+
+    fn right(&self) -> Self {
+        self.left() | U256::from(1)
+    }
+
+    fn parent(&self) -> Self {
+        self >> 1
+    }
+
+    fn right_index(&self, max_depth: u8) -> Self {
+        let remaining = (max_depth - self.depth()) as usize;
+        (self << remaining) | ((U256::from(1) << remaining) - U256::from(1))
+    }
+
+    fn trace_index(&self, max_depth: u8) -> anyhow::Result<u64> {
+        self.right_index(max_depth).index_at_depth().map_err(|_| {
+            anyhow::anyhow!(
+                "Trace index of position {} at max depth {} does not fit in a u64",
+                self,
+                max_depth
+            )
+        })
+    }
+
+    fn make_move(&self, is_attack: bool) -> Self {
+        (U256::from(!is_attack as u8) | self) << 1
+    }
+}
+
 impl ChessClock for Clock {
     fn duration(&self) -> u64 {
         (self >> 64) as u64
@@ -95,7 +543,23 @@ impl ChessClock for Clock {
 #[cfg(test)]
 mod test {
     use super::ChessClock;
-    use super::{Gindex, Position};
+    #[cfg(feature = "wide-position")]
+    use super::{checked_gindex, compute_gindex, U256};
+    use super::{claims_equal_ignoring_status, Gindex, Position, VMStatus};
+    use durin_primitives::Claim;
+
+    #[test]
+    fn claims_equal_ignoring_status_byte() {
+        let mut a = Claim::repeat_byte(0xFF);
+        let mut b = a;
+        a[0] = VMStatus::Invalid as u8;
+        b[0] = VMStatus::Valid as u8;
+        assert_ne!(a, b);
+        assert!(claims_equal_ignoring_status(&a, &b));
+
+        b[31] = 0;
+        assert!(!claims_equal_ignoring_status(&a, &b));
+    }
 
     #[test]
     fn chess_clock_correctness() {
@@ -104,6 +568,42 @@ mod test {
         assert_eq!(clock.timestamp(), 5764607523034234881);
     }
 
+    #[test]
+    fn chess_clock_freeze_period() {
+        // 10 seconds elapsed, last stopped at timestamp 100.
+        let clock = (10u128 << 64) | 100;
+        let (freeze_start, freeze_duration) = (150, 50);
+
+        assert!(!clock.is_paused(149, freeze_start, freeze_duration));
+        assert!(clock.is_paused(150, freeze_start, freeze_duration));
+        assert!(clock.is_paused(199, freeze_start, freeze_duration));
+        assert!(!clock.is_paused(200, freeze_start, freeze_duration));
+
+        // Time within the freeze period is not counted.
+        assert_eq!(clock.duration_at(150, freeze_start, freeze_duration), 60);
+        assert_eq!(clock.duration_at(175, freeze_start, freeze_duration), 60);
+        assert_eq!(clock.duration_at(200, freeze_start, freeze_duration), 60);
+        assert_eq!(clock.duration_at(210, freeze_start, freeze_duration), 70);
+        assert_eq!(clock.duration_at(210, 0, 0), 120);
+
+        // A freeze period that started before the clock was stopped only pauses it once the
+        // clock is running.
+        assert_eq!(clock.duration_at(120, 90, 20), 20);
+
+        assert_eq!(clock.time_left(175, 100, freeze_start, freeze_duration), 40);
+        assert!(!clock.is_expired(220, 100, freeze_start, freeze_duration));
+        assert!(clock.is_expired(240, 100, freeze_start, freeze_duration));
+        assert!(clock.is_expired(190, 100, 0, 0));
+
+        // A clock that has run for the maximum duration saturates rather than overflowing.
+        let clock = ((u64::MAX as u128) << 64) | 100;
+        assert_eq!(
+            clock.duration_at(210, freeze_start, freeze_duration),
+            u64::MAX
+        );
+        assert!(clock.is_expired(210, 100, freeze_start, freeze_duration));
+    }
+
     /// A helper struct for testing the [Position] trait implementation for [std::u128].
     /// 0. `u64` - `depth`
     /// 1. `u64` - `index_at_depth`
@@ -149,12 +649,119 @@ mod test {
     #[test]
     fn position_correctness_static() {
         for (p, v) in EXPECTED_VALUES.iter().enumerate() {
-            let pos = (p + 1) as Position;
+            let pos = Position::from(p as u128 + 1);
             assert_eq!(pos.depth(), v.0);
-            assert_eq!(pos.index_at_depth(), v.1);
+            assert_eq!(pos.index_at_depth().unwrap(), v.1);
             let r = pos.right_index(MAX_DEPTH);
-            assert_eq!(r, v.2);
-            assert_eq!(r.index_at_depth(), v.3);
+            assert_eq!(r, Position::from(v.2));
+            assert_eq!(r.index_at_depth().unwrap(), v.3);
         }
     }
+
+    #[test]
+    fn zero_position_depth() {
+        let zero = Position::from(0);
+        assert!(!zero.is_valid());
+        assert_eq!(zero.depth(), 0);
+        assert_eq!(zero.index_at_depth().unwrap(), 0);
+        assert_eq!(0u128.depth(), 0);
+    }
+
+    #[test]
+    fn position_trace_range() {
+        assert_eq!(Position::from(1).trace_range(MAX_DEPTH).unwrap(), 0..=15);
+        assert_eq!(Position::from(2).trace_range(MAX_DEPTH).unwrap(), 0..=7);
+        assert_eq!(Position::from(3).trace_range(MAX_DEPTH).unwrap(), 8..=15);
+        assert_eq!(Position::from(13).trace_range(MAX_DEPTH).unwrap(), 10..=11);
+        for pos in (16..32).map(Position::from) {
+            let trace_index = pos.trace_index(MAX_DEPTH).unwrap();
+            assert_eq!(
+                pos.trace_range(MAX_DEPTH).unwrap(),
+                trace_index..=trace_index
+            );
+        }
+    }
+
+    #[test]
+    fn deep_trace_index() {
+        // Trace indices at the mainnet max game depth of 73 do not all fit within a u64.
+        let max_depth = 73;
+        let leftmost = super::compute_gindex(max_depth, 0);
+        assert_eq!(leftmost.index_at_depth().unwrap(), 0);
+        assert_eq!(leftmost.trace_index(max_depth).unwrap(), 0);
+        assert_eq!(
+            (leftmost + u64::MAX as u128)
+                .trace_index(max_depth)
+                .unwrap(),
+            u64::MAX
+        );
+        assert_eq!(
+            Position::ROOT
+                .trace_index(max_depth)
+                .unwrap_err()
+                .to_string(),
+            "Trace index of position 1 at max depth 73 does not fit in a u64"
+        );
+        assert!(Position::ROOT
+            .trace_range(max_depth)
+            .unwrap_err()
+            .to_string()
+            .contains("does not fit in a u64"));
+    }
+
+    #[test]
+    #[cfg(feature = "wide-position")]
+    fn wide_position_correctness_static() {
+        for (p, v) in EXPECTED_VALUES.iter().enumerate() {
+            let pos = U256::from(p + 1);
+            assert_eq!(pos.depth(), v.0);
+            assert_eq!(pos.index_at_depth().unwrap(), v.1);
+            let r = pos.right_index(MAX_DEPTH);
+            assert_eq!(r, U256::from(v.2));
+            assert_eq!(r.index_at_depth().unwrap(), v.3);
+            assert_eq!(pos.trace_index(MAX_DEPTH).unwrap(), v.3);
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "wide-position")]
+    fn wide_position_deep() {
+        let max_depth = 200;
+        let root = Position::ROOT;
+        assert_eq!(root.right_index(max_depth).depth(), max_depth);
+        assert_eq!(
+            root.trace_index(max_depth).unwrap_err().to_string(),
+            format!(
+                "Trace index of position 1 at max depth {} does not fit in a u64",
+                max_depth
+            )
+        );
+        assert_eq!(
+            root.right_index(max_depth)
+                .index_at_depth()
+                .unwrap_err()
+                .to_string(),
+            format!(
+                "Index at depth of position {} does not fit in a u64",
+                root.right_index(max_depth)
+            )
+        );
+
+        // Attack all the way down the left side of the tree.
+        let mut pos = root;
+        for _ in 0..max_depth {
+            pos = pos.make_move(true);
+        }
+        assert_eq!(*pos, U256::from(1) << max_depth as usize);
+        assert_eq!(pos, compute_gindex(max_depth, 0));
+        assert_eq!(checked_gindex(max_depth, 0).unwrap(), pos);
+        assert_eq!(pos.depth(), max_depth);
+        assert_eq!(pos.index_at_depth().unwrap(), 0);
+        assert_eq!(pos.trace_index(max_depth).unwrap(), 0);
+        assert_eq!(pos.parent().depth(), max_depth - 1);
+
+        // A defense moves to the right of the parent's sibling.
+        let defended = root.make_move(true).make_move(false);
+        assert_eq!(defended, Position::from(6));
+    }
 }
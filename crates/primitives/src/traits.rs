@@ -29,8 +29,11 @@ pub trait DisputeGame {
     /// Returns the current status of the dispute game.
     fn status(&self) -> &GameStatus;
 
-    /// Resolves the dispute game, returning the [GameStatus] after resolution.
-    fn resolve(&mut self) -> &GameStatus;
+    /// Resolves the dispute game, returning the [GameStatus] after resolution. If `sim`
+    /// is true, the resulting [GameStatus] is computed without being written back to the
+    /// dispute game. Returns an error if the dispute game cannot be resolved, in which case
+    /// its status is left unchanged.
+    fn resolve(&mut self, sim: bool) -> anyhow::Result<GameStatus>;
 }
 
 /// The [DisputeSolver] trait describes the base functionality of a solver for